@@ -0,0 +1,94 @@
+//! Compile-time companion to `csv2serde`, generating the types of a CSV file
+//! where they are used, so they can't get out of sync with the file.
+
+use std::path::{Path, PathBuf};
+
+use convert_case::{Case, Casing};
+use csv2serde::Config;
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, Ident, LitStr, Token,
+};
+
+/// The path of the file, and optionally the name of the type.
+struct Input {
+    path: LitStr,
+    name: Option<Ident>,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        let name = if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        Ok(Input { path, name })
+    }
+}
+
+/// Expands into the struct inferred from a CSV file, at a path relative to
+/// the crate root. The struct is named after the file unless a name is given.
+///
+/// ```ignore
+/// use serde::Deserialize;
+///
+/// csv2serde_macros::csv_schema!("data/orders.csv");
+/// csv2serde_macros::csv_schema!("data/orders.csv", Order);
+/// ```
+#[proc_macro]
+pub fn csv_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as Input);
+
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(message) => syn::Error::new(input.path.span(), message)
+            .to_compile_error()
+            .into(),
+    }
+}
+
+fn expand(input: &Input) -> Result<proc_macro2::TokenStream, String> {
+    let root = std::env::var("CARGO_MANIFEST_DIR").map_err(|e| e.to_string())?;
+    let path = PathBuf::from(root).join(input.path.value());
+
+    let struct_name = match &input.name {
+        Some(name) => name.to_string(),
+        None => struct_name(&path)?,
+    };
+    let delimiter = match path.extension() {
+        Some(ext) if ext == "tsv" => b'\t',
+        _ => b',',
+    };
+    let config = Config::builder()
+        .struct_name(struct_name)
+        .delimiter(delimiter)
+        .with_imports(false)
+        .build();
+
+    let reader = config
+        .reader_builder()
+        .from_path(&path)
+        .map_err(|e| format!("Could not read '{}': {e}", path.display()))?;
+    let code = csv2serde::run(reader, &config).map_err(|e| e.to_string())?;
+    let code = code
+        .parse::<proc_macro2::TokenStream>()
+        .map_err(|e| e.to_string())?;
+
+    // Rebuilds whenever the file changes.
+    let path = path.display().to_string();
+    Ok(quote! {
+        const _: &[u8] = include_bytes!(#path);
+        #code
+    })
+}
+
+fn struct_name(path: &Path) -> Result<String, String> {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_case(Case::Pascal))
+        .ok_or_else(|| format!("Could not name a type after '{}'", path.display()))
+}
@@ -0,0 +1,38 @@
+//! The macro expanded on a checked-in file, and the file read with the
+//! types it expanded into.
+
+use serde::Deserialize;
+
+csv2serde_macros::csv_schema!("tests/data/orders.csv");
+csv2serde_macros::csv_schema!("tests/data/orders.csv", Order);
+
+fn read<T: for<'de> Deserialize<'de>>() -> Vec<T> {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/orders.csv");
+    csv::Reader::from_path(path)
+        .unwrap()
+        .deserialize()
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+#[test]
+fn named_after_file() {
+    let rows = read::<Orders>();
+    assert_eq!(
+        rows.iter()
+            .map(|r| (r.id, r.sku.as_str(), r.total, r.note.as_deref()))
+            .collect::<Vec<_>>(),
+        [(1, "A-1", 9.5, None), (2, "B-2", 12.0, Some("gift"))]
+    );
+}
+
+#[test]
+fn named() {
+    let rows = read::<Order>();
+    assert_eq!(
+        rows.iter()
+            .map(|r| (r.id, r.sku.as_str(), r.total, r.note.as_deref()))
+            .collect::<Vec<_>>(),
+        [(1, "A-1", 9.5, None), (2, "B-2", 12.0, Some("gift"))]
+    );
+}
@@ -0,0 +1,5 @@
+//! Run as `cargo csv2serde`, which cargo finds by this name.
+
+fn main() {
+    csv2serde::cli::main();
+}
@@ -0,0 +1,109 @@
+//! Generating code from `build.rs`, into `OUT_DIR`:
+//!
+//! ```no_run
+//! // build.rs
+//! let out_dir = std::env::var("OUT_DIR").unwrap();
+//! let out_path = std::path::Path::new(&out_dir).join("orders.rs");
+//! let config = csv2serde::Config::builder().struct_name("Order").build();
+//!
+//! csv2serde::build::generate_to("data/orders.csv", out_path, &config).unwrap();
+//! ```
+//!
+//! The crate then includes the generated code with
+//! `include!(concat!(env!("OUT_DIR"), "/orders.rs"));`.
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fs,
+    hash::{Hash, Hasher},
+    path::Path,
+};
+
+use crate::{Config, Error};
+
+/// Generates code for the CSV file at `csv_path` into `out_path`, and tells
+/// Cargo to rerun the build script when the file changes.
+///
+/// The output records a hash of the input and the config, so it is only
+/// regenerated when either changed. Returns whether it was.
+pub fn generate_to(
+    csv_path: impl AsRef<Path>,
+    out_path: impl AsRef<Path>,
+    config: &Config,
+) -> Result<bool, Error> {
+    let (csv_path, out_path) = (csv_path.as_ref(), out_path.as_ref());
+    println!("cargo:rerun-if-changed={}", csv_path.display());
+
+    let csv = fs::read(csv_path).map_err(|source| Error::CantReadFile {
+        path: csv_path.to_path_buf(),
+        source,
+    })?;
+
+    // Formats without comments have nowhere to record the hash.
+    let stamp = config.emit.line_comment().map(|comment| {
+        format!(
+            "{comment} csv2serde input hash: {:016x}\n",
+            hash(&csv, config)
+        )
+    });
+    if let Some(stamp) = &stamp {
+        let existing = fs::read_to_string(out_path).unwrap_or_default();
+        if existing.starts_with(stamp.as_str()) {
+            return Ok(false);
+        }
+    }
+
+    let reader = config.reader_builder().from_reader(csv.as_slice());
+    let code = crate::run(reader, config)?;
+    let code = stamp.unwrap_or_default() + &code;
+
+    fs::write(out_path, code).map_err(|source| Error::CantWriteFile {
+        path: out_path.to_path_buf(),
+        source,
+    })?;
+    Ok(true)
+}
+
+fn hash(csv: &[u8], config: &Config) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    csv.hash(&mut hasher);
+
+    // Hash maps are listed in a different order on every run.
+    let mut overrides = config.type_overrides.iter().collect::<Vec<_>>();
+    overrides.sort();
+    overrides.hash(&mut hasher);
+    let config = Config {
+        type_overrides: HashMap::new(),
+        ..config.clone()
+    };
+    format!("{config:?}").hash(&mut hasher);
+
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate_to;
+    use crate::Config;
+
+    #[test]
+    fn regenerates_on_change() {
+        let dir = std::env::temp_dir().join(format!("csv2serde-build-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (csv_path, out_path) = (dir.join("orders.csv"), dir.join("orders.rs"));
+        let config = Config::default();
+
+        std::fs::write(&csv_path, "id\n1\n").unwrap();
+        assert!(generate_to(&csv_path, &out_path, &config).unwrap());
+        assert!(!generate_to(&csv_path, &out_path, &config).unwrap());
+
+        std::fs::write(&csv_path, "id\n-1\n").unwrap();
+        assert!(generate_to(&csv_path, &out_path, &config).unwrap());
+        assert!(std::fs::read_to_string(&out_path).unwrap().contains("i8"));
+
+        let config = Config::builder().struct_name("Order").build();
+        assert!(generate_to(&csv_path, &out_path, &config).unwrap());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
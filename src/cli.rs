@@ -0,0 +1,1322 @@
+//! The command line tool, shared by the `csv2serde` and `cargo-csv2serde`
+//! binaries.
+
+mod cargo;
+mod check;
+mod compression;
+mod config_file;
+mod diagnostic;
+mod inputs;
+mod interactive;
+mod out_dir;
+mod progress;
+mod provenance;
+mod reader_source;
+mod replace_delimiter;
+mod rustfmt;
+mod scaffold;
+mod skip_rows;
+mod sniff;
+mod splice;
+mod watch;
+mod write_destination;
+
+#[cfg(feature = "xlsx")]
+mod workbook;
+
+use crate::{
+    Config, Emit, EmptyColumns, ExtraColumns, FieldCase, ReportFormat, RowFilter, Sampling,
+    SqlDialect, StructCase, Trim, Visibility,
+};
+use clap::{error::ErrorKind, parser::ValueSource, CommandFactory, FromArgMatches, Parser};
+use convert_case::{Case, Casing};
+use encoding_rs::Encoding;
+use encoding_rs_io::DecodeReaderBytesBuilder;
+use regex::Regex;
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    ffi::OsString,
+    io::{self, IsTerminal, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use compression::{Compression, Decoder};
+use config_file::ConfigFile;
+use progress::Progress;
+use reader_source::ReaderSource;
+use replace_delimiter::ReplaceDelimiter;
+use skip_rows::SkipRows;
+use write_destination::WriteDestination;
+
+#[derive(Debug, Parser)]
+#[command(author, version, about, subcommand_negates_reqs = true)]
+pub struct CLI {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Files for which types will be generated, one struct per file.
+    /// With the `remote` feature, these can also be HTTP(S) URLs.
+    /// If not provided, output will be sent to stdout.
+    /// Glob patterns are expanded if the shell did not do so.
+    files: Vec<PathBuf>,
+
+    /// Read defaults for these options from this file, rather than from the nearest
+    /// `csv2serde.toml` in the current directory or above. Options are keyed by their long
+    /// names, and a `[names]` table names the structs of particular inputs.
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    #[arg(skip)]
+    config_file: ConfigFile,
+
+    /// Write the options in effect, from the command line and any config file, to this file
+    /// in the format `--config` reads. Input files aren't saved.
+    #[arg(long, value_name = "FILE")]
+    save_config: Option<PathBuf>,
+
+    /// Directory to search recursively for CSV and TSV files.
+    #[arg(long)]
+    dir: Vec<PathBuf>,
+
+    /// Map input files into memory instead of reading them, which is faster for
+    /// multi-gigabyte files. Requires the `mmap` feature.
+    #[arg(long)]
+    mmap: bool,
+
+    /// Worksheet to read from Excel or OpenDocument inputs, defaults to the first one.
+    /// Requires the `xlsx` feature.
+    #[arg(long)]
+    sheet: Option<String>,
+
+    /// Treat all inputs as one dataset sharing the same headers, generating a single struct.
+    #[arg(long)]
+    merge: bool,
+
+    /// Name of the type, defaults to filename. Only valid for a single input,
+    /// or with `--merge`.
+    #[arg(short = 'n', long, required_unless_present_any(["files", "dir", "from_schema"]))]
+    name: Option<String>,
+
+    /// Naming convention of type names derived from file names.
+    #[arg(long, value_enum, default_value_t = StructCase::Pascal)]
+    struct_case: StructCase,
+
+    /// Prepended to type names derived from file names.
+    #[arg(long, default_value_t)]
+    prefix: String,
+
+    /// Appended to type names derived from file names, e.g. `Row` for `OrdersRow`.
+    #[arg(long, default_value_t)]
+    suffix: String,
+
+    /// Instead of writing the output, compare it with this file. Prints a diff
+    /// and exits with an error if the file is out of date.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["output", "scaffold"])]
+    check: Option<PathBuf>,
+
+    /// Save what was inferred to this JSON file, to generate code from later
+    /// with `--from-schema`.
+    #[arg(long, value_name = "FILE")]
+    save_schema: Option<PathBuf>,
+
+    /// Generate code from a schema saved with `--save-schema`, instead of
+    /// reading any input.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["files", "dir", "merge", "save_schema"])]
+    from_schema: Option<PathBuf>,
+
+    /// File into which the types will be written.
+    #[arg(short = 'o', long, group = "files_out")]
+    output: Option<PathBuf>,
+
+    /// Write one module per input into this directory instead, named after its type, with a
+    /// `mod.rs` re-exporting them all. Existing files are only replaced with `--force`.
+    #[arg(
+        long,
+        value_name = "DIR",
+        group = "files_out",
+        conflicts_with_all = ["output", "check", "scaffold", "merge", "report", "watch"]
+    )]
+    out_dir: Option<PathBuf>,
+
+    /// Write a cargo project into this directory instead, with the generated types in
+    /// `src/model.rs` and a `src/main.rs` counting rows. Existing files are never overwritten.
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["output", "borrow"])]
+    scaffold: Option<PathBuf>,
+
+    /// After inference, go through the columns one by one, to accept, rename or retype them,
+    /// or toggle whether they're optional, before generating code.
+    #[arg(long)]
+    interactive: bool,
+
+    /// Instead of the types, write a report of how each column's type was inferred: the
+    /// types ruled out and by which value, the number of empty cells, and sample values.
+    #[arg(long, conflicts_with_all = ["check", "scaffold", "suggest_mod"])]
+    report: bool,
+
+    /// Layout of the report.
+    #[arg(long, value_enum, default_value_t, requires = "report")]
+    report_format: ReportFormat,
+
+    /// Keep running, and write the output again whenever an input file changes, printing
+    /// how the schema changed.
+    #[arg(long, requires = "output", conflicts_with_all = ["check", "scaffold", "interactive", "from_schema"])]
+    watch: bool,
+
+    /// After writing into a file, print the `mod` declaration adding it to the crate.
+    #[arg(long, requires = "output")]
+    suggest_mod: bool,
+
+    /// Explain inference decisions on stderr: which value ruled out each type and when
+    /// columns became optional. Repeat for more detail.
+    #[arg(short = 'v', long, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Don't show a progress bar while reading input files, or the delimiter detected in them.
+    #[arg(short = 'q', long)]
+    quiet: bool,
+
+    /// Add the output to the end of an existing file, between marker comments, rather than
+    /// replacing the file. Imports the file already has are left out.
+    #[arg(long, requires = "output", conflicts_with_all = ["check", "scaffold", "watch"])]
+    append: bool,
+
+    /// Replace only this struct in an existing Rust file, keeping the rest of the file.
+    /// The new struct is put between marker comments, which are replaced after that.
+    #[arg(
+        long,
+        value_name = "NAME",
+        requires = "output",
+        conflicts_with_all = ["append", "check", "scaffold", "watch"]
+    )]
+    replace_struct: Option<String>,
+
+    /// If writing into files, overwrite content rather than error out.
+    #[arg(short = 'f', long, requires = "files_out")]
+    force: bool,
+
+    /// Character or string used as delimiter.
+    /// If not provided, it is detected from the input, or a tab for `.tsv` files.
+    #[arg(short = 'd', long, value_parser = clap::builder::NonEmptyStringValueParser::new())]
+    delimiter: Option<String>,
+
+    /// Number of rows to analyze for field type prediction. [default: ALL]
+    #[arg(short = 'l', long, default_value_t = usize::MAX, hide_default_value = true)]
+    lines: usize,
+
+    /// Which of the analyzed rows to use for type prediction: `all`, `random:N`
+    /// to pick N rows across the whole file, or `every:N` to use every Nth row.
+    #[arg(long, default_value = "all", hide_default_value = true)]
+    sample: Sampling,
+
+    /// Number of rows to drop from the end of the file, e.g. totals or export notes.
+    #[arg(long, default_value_t = 0)]
+    skip_footer: usize,
+
+    /// Also drop trailing rows matching this regex, e.g. `^TOTAL`.
+    /// Rows are matched with their fields joined by commas.
+    #[arg(long, value_name = "REGEX")]
+    footer_pattern: Option<Regex>,
+
+    /// Only use rows meeting this condition for type prediction,
+    /// e.g. `country == "US"` or `status != cancelled`. Can be repeated.
+    #[arg(long, value_name = "EXPR")]
+    filter: Vec<RowFilter>,
+
+    /// Stop analyzing once no field type has changed for this many consecutive rows.
+    /// Much faster on huge files, at the risk of missing rare values.
+    #[arg(long, value_name = "ROWS")]
+    converge: Option<usize>,
+
+    /// Analyze rows on this many threads, or 0 for one per CPU. Faster on huge files
+    /// with many columns.
+    #[arg(
+        short = 'j',
+        long,
+        value_name = "N",
+        default_value_t = 1,
+        conflicts_with = "converge"
+    )]
+    jobs: usize,
+
+    /// Generate a struct per value of this column, wrapped in an enum tagged by it.
+    #[arg(long, value_name = "COLUMN", conflicts_with = "borrow")]
+    split_by: Option<String>,
+
+    /// Skips lines with a number of fields less or equal to this number.
+    /// Useful when you want to omit subsection headers.
+    #[arg(short = 's', long, default_value_t = 0)]
+    min_fields: usize,
+
+    /// Skips lines with more non-empty fields than this number.
+    /// Useful when you want to omit notes spilling over into extra columns.
+    #[arg(long)]
+    max_fields: Option<usize>,
+
+    /// Skips lines matching this regex, e.g. repeated header rows or page footers
+    /// interleaved with the data. Rows are matched with their fields joined by commas.
+    #[arg(long, value_name = "REGEX")]
+    skip_matching: Option<Regex>,
+
+    /// Traits to derive in addition to `Debug` and `Deserialize`, e.g. `Serialize,Clone`.
+    #[arg(long, value_name = "TRAITS", value_delimiter = ',', value_parser = parse_derive)]
+    derive: Vec<String>,
+
+    /// Visibility of the generated types and their fields.
+    #[arg(long, value_enum, default_value_t = Visibility::Pub)]
+    visibility: Visibility,
+
+    /// Naming convention of the generated field names.
+    #[arg(long, value_enum, default_value_t = FieldCase::Snake)]
+    field_case: FieldCase,
+
+    /// Prepended to field names that would start with a digit, like `2023_revenue`.
+    #[arg(long, value_name = "PREFIX", default_value = "n_", value_parser = clap::builder::NonEmptyStringValueParser::new())]
+    digit_prefix: String,
+
+    /// Keep non-ASCII letters in field names, like `straße`, rather than
+    /// transliterating them to `strasse`.
+    #[arg(long)]
+    allow_unicode_idents: bool,
+
+    /// Document each field with the values seen: samples, range, distinct count and nulls.
+    #[arg(long)]
+    stats_docs: bool,
+
+    /// Add `#[serde(default)]` to optional fields, so missing columns deserialize as `None`.
+    #[arg(long)]
+    serde_default: bool,
+
+    /// Add `#[serde(skip_serializing_if = "Option::is_none")]` to optional fields. Ignored with
+    /// `--with-writer`, as CSV rows need a cell for every column.
+    #[arg(long)]
+    skip_serializing_none: bool,
+
+    /// Leave out `#[serde(rename)]` attributes, documenting each renamed field's column instead.
+    ///
+    /// Records then deserialize by header only once the headers are changed to match the field
+    /// names, or by position, with `has_headers(false)` or `StringRecord::deserialize(None)`.
+    #[arg(long)]
+    no_rename: bool,
+
+    /// Order struct fields alphabetically rather than by column, so reordering the columns of the
+    /// input doesn't reshuffle the output. Records still deserialize by header, thanks to renames.
+    #[arg(long, conflicts_with_all = ["no_rename", "no_headers"])]
+    sort_fields: bool,
+
+    /// Generate `&'a str` fields instead of `String`, for zero-copy deserialization.
+    #[arg(long)]
+    borrow: bool,
+
+    /// Group columns sharing a prefix, like `address_street` and `address_city`, into a
+    /// nested struct flattened into the parent. Takes the separator, `_` by default.
+    #[arg(long, value_name = "SEPARATOR", num_args = 0..=1, default_missing_value = "_", value_parser = clap::builder::NonEmptyStringValueParser::new())]
+    nest_prefixes: Option<String>,
+
+    /// Generate a function reading a whole file into a `Vec` of the generated type, with the
+    /// delimiter and quoting settings used here.
+    #[arg(long)]
+    with_reader: bool,
+
+    /// Generate a function returning an iterator over the records of a file, deserializing them
+    /// one at a time.
+    #[arg(long)]
+    with_iter: bool,
+
+    /// Generate a function writing a slice of the generated type to a file, in the same dialect,
+    /// deriving `Serialize` for it. The csv crate can't write the structs nested by
+    /// `--nest-prefixes`.
+    #[arg(long, conflicts_with = "nest_prefixes")]
+    with_writer: bool,
+
+    /// Generate a `validate` method for each type, rejecting records outside what was seen here:
+    /// numbers out of the observed range, strings longer than the longest one, or, for columns
+    /// with a few repeated values, any other value.
+    #[arg(long)]
+    with_validation: bool,
+
+    /// Append a test module deserializing the first few sampled rows, embedded in the output,
+    /// into the generated types.
+    #[arg(long)]
+    with_tests: bool,
+
+    /// Start the output with `use` statements for `Deserialize` and any other types it
+    /// refers to. On by default, pass `--with-imports=false` to leave them out.
+    #[arg(long, default_value_t = true, num_args = 0..=1, default_missing_value = "true", action = clap::ArgAction::Set)]
+    with_imports: bool,
+
+    /// Start the output with comments recording the inputs and their hashes, the time, the
+    /// version of csv2serde and the command generating it.
+    #[arg(long, conflicts_with = "check")]
+    provenance: bool,
+
+    /// How to format generated Rust code.
+    #[arg(long, value_enum, default_value_t)]
+    fmt: Fmt,
+
+    /// Format of the output. Options specific to Rust are ignored by the others.
+    #[arg(long, value_enum, default_value_t = Emit::Rust)]
+    emit: Emit,
+
+    /// Dialect of `--emit sql` output, and of `--insert-template`.
+    #[arg(long, value_enum, default_value_t = SqlDialect::Postgres)]
+    sql_dialect: SqlDialect,
+
+    /// With `--emit arrow`, also generate a function converting the CSV file to Parquet.
+    #[arg(long)]
+    parquet_example: bool,
+
+    /// With `--emit sqlx`, also generate an `INSERT` statement with a placeholder per column,
+    /// in the style of `--sql-dialect`.
+    #[arg(long)]
+    insert_template: bool,
+
+    /// With `--emit diesel`, the column holding the primary key. By default, `id`, or else the
+    /// first column whose values were all distinct.
+    #[arg(long, value_name = "COLUMN")]
+    primary_key: Option<String>,
+
+    /// Add blank lines between struct fields.
+    #[arg(short = 'b', long, default_value_t = 1)]
+    blank_lines: usize,
+
+    /// Detect `k1=v1;k2=v2` cells and generate `HashMap<String, String>` fields.
+    #[arg(long)]
+    map_cells: bool,
+
+    /// Detect values like `1h30m`, `90s` or `00:05:12` and generate `Duration` fields.
+    #[arg(long)]
+    durations: bool,
+
+    /// Detect values that look like filesystem paths and generate `PathBuf` fields.
+    #[arg(long)]
+    path_type: bool,
+
+    /// Pin the type of a column, matched by raw header or field name, skipping inference.
+    /// Columns found to be optional are still wrapped in `Option`.
+    #[arg(long = "type", value_name = "COLUMN=TYPE", value_parser = parse_type_override)]
+    types: Vec<(String, String)>,
+
+    /// Columns to always generate as `Option<T>`, regardless of sampled values.
+    #[arg(long, value_name = "COLUMNS", value_delimiter = ',')]
+    optional: Vec<String>,
+
+    /// Columns to never generate as `Option<T>`, regardless of sampled values.
+    #[arg(long, value_name = "COLUMNS", value_delimiter = ',')]
+    required: Vec<String>,
+
+    /// Values standing for a missing value, like `NA` or `NULL`, treated as empty cells.
+    /// Optional fields then deserialize anything they can't parse as `None`.
+    #[arg(long, value_name = "VALUES", value_delimiter = ',')]
+    null: Vec<String>,
+
+    /// Columns to mask as they are deserialized, through a generated `redact_string` helper,
+    /// keeping only the length of their values. Their values are also left out of field docs
+    /// and generated tests.
+    #[arg(long, value_name = "COLUMNS", value_delimiter = ',')]
+    redact: Vec<String>,
+
+    /// Generate every field as `Option<T>`. Columns listed in `--required` are exempt.
+    #[arg(long)]
+    all_optional: bool,
+
+    /// Only make a field optional when more than this fraction of its cells
+    /// are empty, e.g. `0.01`. Columns close to it are reported.
+    #[arg(long, value_name = "FRACTION", default_value_t = 0.0, value_parser = parse_fraction)]
+    optional_threshold: f64,
+
+    /// How to generate columns for which no values were seen.
+    #[arg(long, value_enum, default_value_t = EmptyColumns::Unit)]
+    empty_columns: EmptyColumns,
+
+    /// Leave out columns for which no values were seen, listing them in a warning.
+    /// Short for `--empty-columns skip`.
+    #[arg(long, conflicts_with = "empty_columns")]
+    prune_empty: bool,
+
+    /// Columns to leave out of the generated struct.
+    #[arg(long, value_name = "COLUMNS", value_delimiter = ',')]
+    skip_columns: Vec<String>,
+
+    /// Treat the first row as data rather than headers.
+    /// Fields are named `field_0`, `field_1`, … unless `--headers` is given.
+    #[arg(long)]
+    no_headers: bool,
+
+    /// What to do with fields beyond the header row, in records of a `--flexible` file:
+    /// ignore them with a warning, fail, or capture them as `extra_1`, `extra_2`, … columns of
+    /// type `Option<String>`. Generated readers add them to the headers; elsewhere they're only
+    /// filled when deserializing by position.
+    #[arg(long, value_enum, default_value_t = ExtraColumns::Ignore)]
+    extra_columns: ExtraColumns,
+
+    /// Field names replacing the file's header row.
+    /// Combine with `--no-headers` if the first row holds data.
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    headers: Vec<String>,
+
+    /// Prefix of the names given to columns with a blank header, followed by
+    /// the column's index, as in `column_3`.
+    #[arg(long, value_name = "PREFIX", default_value = "column_")]
+    placeholder_prefix: String,
+
+    /// Character used for quoting fields.
+    #[arg(long, default_value = "\"", value_parser = parse_ascii)]
+    quote: u8,
+
+    /// Character used for escaping quotes inside quoted fields.
+    #[arg(long, value_parser = parse_ascii)]
+    escape: Option<u8>,
+
+    /// Don't treat two consecutive quotes as an escaped quote.
+    #[arg(long)]
+    no_double_quote: bool,
+
+    /// Skip lines starting with this character.
+    #[arg(long, value_parser = parse_ascii)]
+    comment: Option<u8>,
+
+    /// Accept rows with a varying number of fields.
+    /// Missing trailing fields make their columns optional; extra fields are ignored.
+    #[arg(long)]
+    flexible: bool,
+
+    /// Where to trim leading and trailing whitespace. Untrimmed whitespace-only
+    /// cells count as values rather than empty cells.
+    #[arg(long, value_enum, default_value_t = Trim::All)]
+    trim: Trim,
+
+    /// Number of lines to discard before the header row, e.g. a title or metadata preamble.
+    #[arg(long, default_value_t = 0)]
+    skip_rows: usize,
+
+    /// Encoding of the input, e.g. `latin1` or `utf-16le`.
+    /// Defaults to UTF-8, or whatever a byte order mark indicates.
+    #[arg(long, value_parser = parse_encoding)]
+    encoding: Option<&'static Encoding>,
+
+    /// Compression of the input. Guessed from the file extension if not provided.
+    #[arg(long, value_enum)]
+    compression: Option<Compression>,
+}
+
+impl From<&CLI> for Config {
+    fn from(cli: &CLI) -> Self {
+        let struct_name = get_struct_name(cli, cli.files.first());
+
+        Config::builder()
+            .lines(cli.lines)
+            .min_fields(cli.min_fields)
+            .max_fields(cli.max_fields)
+            .skip_matching(cli.skip_matching.clone())
+            .struct_name(struct_name)
+            .blank_lines(cli.blank_lines)
+            .map_cells(cli.map_cells)
+            .durations(cli.durations)
+            .path_type(cli.path_type)
+            .type_overrides(cli.types.iter().cloned().collect::<HashMap<_, _>>())
+            .optional_columns(cli.optional.clone())
+            .required_columns(cli.required.clone())
+            .redact_columns(cli.redact.clone())
+            .null_values(cli.null.clone())
+            .all_optional(cli.all_optional)
+            .optional_threshold(cli.optional_threshold)
+            .empty_columns(if cli.prune_empty {
+                EmptyColumns::Skip
+            } else {
+                cli.empty_columns
+            })
+            .skip_columns(cli.skip_columns.clone())
+            .extra_columns(cli.extra_columns)
+            .headers(cli.headers.clone())
+            .placeholder_prefix(cli.placeholder_prefix.clone())
+            .quote(cli.quote)
+            .escape(cli.escape)
+            .double_quote(!cli.no_double_quote)
+            .comment(cli.comment)
+            .flexible(cli.flexible)
+            .trim(cli.trim)
+            .sample(cli.sample)
+            .skip_footer(cli.skip_footer)
+            .footer_pattern(cli.footer_pattern.clone())
+            .filters(cli.filter.clone())
+            .converge(cli.converge)
+            .jobs(cli.jobs)
+            .split_by(cli.split_by.clone())
+            .derives(cli.derive.clone())
+            .visibility(cli.visibility)
+            .field_case(cli.field_case)
+            .digit_prefix(cli.digit_prefix.clone())
+            .allow_unicode_idents(cli.allow_unicode_idents)
+            .stats_docs(cli.stats_docs)
+            .serde_default(cli.serde_default)
+            .skip_serializing_none(cli.skip_serializing_none)
+            .no_rename(cli.no_rename)
+            .sort_fields(cli.sort_fields)
+            .borrow(cli.borrow)
+            .nest_separator(cli.nest_prefixes.clone())
+            .with_reader(cli.with_reader)
+            .with_iter(cli.with_iter)
+            .with_writer(cli.with_writer)
+            .with_validation(cli.with_validation)
+            .with_tests(cli.with_tests)
+            // A scaffolded project has to compile as-is.
+            .with_imports(cli.with_imports || cli.scaffold.is_some())
+            .emit(cli.emit)
+            .sql_dialect(cli.sql_dialect)
+            .parquet_example(cli.parquet_example)
+            .insert_template(cli.insert_template)
+            .primary_key(cli.primary_key.clone())
+            .delimiter(get_delimiter_byte(cli).unwrap_or(b','))
+            .has_headers(!cli.no_headers)
+            .build()
+    }
+}
+
+fn parse_type_override(arg: &str) -> Result<(String, String), String> {
+    let (column, type_name) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected COLUMN=TYPE, got '{arg}'"))?;
+
+    syn::parse_str::<syn::Type>(type_name)
+        .map_err(|e| format!("invalid type '{type_name}': {e}"))?;
+
+    Ok((column.trim().to_string(), type_name.trim().to_string()))
+}
+
+fn parse_derive(name: &str) -> Result<String, String> {
+    syn::parse_str::<syn::Path>(name).map_err(|e| format!("invalid trait '{name}': {e}"))?;
+    Ok(name.trim().to_string())
+}
+
+fn parse_fraction(arg: &str) -> Result<f64, String> {
+    match arg.parse::<f64>() {
+        Ok(fraction) if (0.0..1.0).contains(&fraction) => Ok(fraction),
+        _ => Err(format!("expected a fraction from 0 up to 1, got '{arg}'")),
+    }
+}
+
+/// The csv reader only takes single bytes for quotes, escapes and comments.
+fn parse_ascii(arg: &str) -> Result<u8, String> {
+    match arg.as_bytes() {
+        [byte] if byte.is_ascii() => Ok(*byte),
+        _ => Err(format!("expected a single ASCII character, got '{arg}'")),
+    }
+}
+
+fn parse_encoding(label: &str) -> Result<&'static Encoding, String> {
+    Encoding::for_label(label.as_bytes()).ok_or_else(|| format!("unknown encoding '{label}'"))
+}
+
+/// For URLs, only the path matters, not the query or fragment.
+fn strip_url_query<P: AsRef<Path>>(path: P) -> PathBuf {
+    if reader_source::is_url(&path) {
+        let url = path.as_ref().to_string_lossy();
+        PathBuf::from(url.split(['?', '#']).next().unwrap_or_default())
+    } else {
+        path.as_ref().to_path_buf()
+    }
+}
+
+fn get_struct_name(cli: &CLI, path: Option<&PathBuf>) -> String {
+    match (&cli.name, path) {
+        (Some(name), _) => name.to_case(Case::Pascal),
+        (None, Some(path)) => {
+            if let Some(name) = cli.config_file.name(path) {
+                return name.to_string();
+            }
+
+            let stem = get_name_from_path(path);
+            let name = match cli.struct_case {
+                StructCase::Pascal => stem.to_case(Case::Pascal),
+                StructCase::Keep => stem.replace(|c: char| !c.is_alphanumeric(), "_"),
+            };
+
+            format!("{}{name}{}", cli.prefix, cli.suffix)
+        }
+        // Types are named as they were when the schema was saved.
+        (None, None) if cli.from_schema.is_some() => String::new(),
+        // Subcommands don't generate types.
+        (None, None) if cli.command.is_some() => String::new(),
+        _ => unreachable!("Name should be required when no path provided."),
+    }
+}
+
+/// Strips the URL query and compression extension, so that `orders.csv.gz` gives `orders.csv`.
+fn get_data_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    let path = strip_url_query(path);
+
+    match Compression::from_path(&path) {
+        Compression::None => path,
+        _ => path.with_extension(""),
+    }
+}
+
+fn get_name_from_path<P: AsRef<Path>>(path: P) -> String {
+    let path = get_data_path(path);
+
+    let stem = path.file_stem();
+    let stem = stem.unwrap_or_else(|| {
+        panic!("Could not parse name from path '{}'", path.display(),);
+    });
+
+    stem.to_string_lossy().to_string()
+}
+
+/// The single byte the csv reader should split on, if the delimiter was provided.
+pub fn get_delimiter_byte(cli: &CLI) -> Option<u8> {
+    cli.delimiter.as_ref().map(|d| match d.as_bytes() {
+        [byte] => *byte,
+        _ => replace_delimiter::REPLACEMENT,
+    })
+}
+
+/// Opens the input, returning a reader along with the delimiter it uses.
+fn open_reader(cli: &CLI, config: &Config, path: Option<&PathBuf>) -> (csv::Reader<impl Read>, u8) {
+    let source = ReaderSource::open(cli, path).expect("Failed to read input.");
+    let progress = progress(cli, path);
+    let source = match &progress {
+        Some(progress) => Box::new(progress.bytes(source)) as Box<dyn Read>,
+        None => Box::new(source),
+    };
+    let compression = match (cli.compression, path) {
+        (Some(compression), _) => compression,
+        (None, Some(path)) => Compression::from_path(strip_url_query(path)),
+        (None, None) => Compression::None,
+    };
+    let source = Decoder::new(source, compression).expect("Failed to decompress input.");
+    let source = DecodeReaderBytesBuilder::new()
+        .encoding(cli.encoding)
+        .build(source);
+    let source = match &progress {
+        Some(progress) => Box::new(progress.rows(source)) as Box<dyn Read>,
+        None => Box::new(source),
+    };
+    let source = SkipRows::new(source, cli.skip_rows);
+
+    // Multi-byte delimiters are rewritten to a single byte the csv crate can handle.
+    let multi_byte_delimiter = cli.delimiter.as_ref().filter(|d| d.len() > 1);
+    let mut source = match multi_byte_delimiter {
+        Some(delimiter) => {
+            ReplaceDelimiter::new(source, delimiter.as_bytes(), cli.quote, cli.escape)
+        }
+        None => ReplaceDelimiter::new(source, &[], cli.quote, cli.escape),
+    };
+
+    let is_tsv = path.is_some_and(|p| get_data_path(p).extension().is_some_and(|e| e == "tsv"));
+    let mut sample = vec![];
+
+    let delimiter = match cli.delimiter {
+        Some(_) => get_delimiter_byte(cli).unwrap(),
+        None if is_tsv => b'\t',
+        None => {
+            (&mut source)
+                .take(8192)
+                .read_to_end(&mut sample)
+                .expect("Failed to read input.");
+
+            let delimiter = sniff::sniff_delimiter(&sample).unwrap_or(b',');
+            if !cli.quiet {
+                eprintln!("Detected delimiter: {:?}", delimiter as char);
+            }
+            delimiter
+        }
+    };
+
+    // Put the sampled bytes back in front of the rest of the input.
+    let source = io::Cursor::new(sample).chain(source);
+
+    let reader = config
+        .reader_builder()
+        .delimiter(delimiter)
+        .from_reader(source);
+
+    (reader, delimiter)
+}
+
+/// Lists the columns of each table for which no values were seen, which are
+/// either left out or generated with a placeholder type.
+fn warn_empty_columns(config: &Config, schema: &crate::Schema) {
+    for table in &schema.tables {
+        let empty = table
+            .fields()
+            .into_iter()
+            .filter(|f| f.is_empty() && !f.is_listed(&config.skip_columns))
+            .map(|f| f.raw_name)
+            .collect::<Vec<_>>();
+
+        if empty.is_empty() {
+            continue;
+        }
+
+        let count = match empty.len() {
+            1 => "1 empty column".to_string(),
+            n => format!("{n} empty columns"),
+        };
+        let (name, columns) = (&table.name, empty.join(", "));
+
+        match config.empty_columns {
+            EmptyColumns::Skip => eprintln!("warning: {name} has {count}, left out: {columns}"),
+            EmptyColumns::Unit => eprintln!(
+                "warning: {name} has {count}, generated as `Option<()>`: {columns}. \
+                 Use --prune-empty to leave them out."
+            ),
+            EmptyColumns::OptionString => {}
+        }
+    }
+}
+
+/// A progress bar for reading a local file, unless it would get in the way:
+/// with `--quiet`, or while writing to a pipe. Nothing is drawn unless stderr
+/// is a terminal.
+fn progress(cli: &CLI, path: Option<&PathBuf>) -> Option<Progress> {
+    let piped = cli.output.is_none() && !io::stdout().is_terminal();
+    if cli.quiet || piped {
+        return None;
+    }
+
+    let path = path.filter(|p| !reader_source::is_url(p) && !reader_source::is_workbook(p))?;
+    let len = std::fs::metadata(path).ok()?.len();
+    Some(Progress::new(len))
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum Command {
+    /// Compare the schemas inferred from two files, exiting with an error if
+    /// any column was added, removed, retyped, or changed optionality.
+    Diff {
+        old: PathBuf,
+        new: PathBuf,
+
+        /// Format of the report.
+        #[arg(long, value_enum, default_value_t = DiffFormat::Text)]
+        format: DiffFormat,
+    },
+    /// Write a CSV file of made-up records shaped like the input, to share
+    /// as a fixture instead of data that may hold personal information.
+    /// Numbers fall in the observed ranges, columns with a few repeated
+    /// values pick from them, and text is random.
+    Fake {
+        file: PathBuf,
+
+        /// Number of records to generate.
+        #[arg(long, default_value_t = 100)]
+        rows: usize,
+
+        /// Seed for the random values, to generate the same records again.
+        /// Random by default.
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+}
+
+/// How generated Rust code is formatted.
+#[derive(Copy, Clone, Debug, Default, PartialEq, clap::ValueEnum)]
+enum Fmt {
+    /// Built in, close to rustfmt's defaults.
+    #[default]
+    Prettyplease,
+    /// Run rustfmt, following the project's `rustfmt.toml`. Falls back to prettyplease if
+    /// rustfmt fails.
+    Rustfmt,
+}
+
+#[derive(Copy, Clone, Debug, clap::ValueEnum)]
+enum DiffFormat {
+    Text,
+    Json,
+}
+
+fn fake(cli: &CLI, path: &PathBuf, rows: usize, seed: Option<u64>) {
+    let mut config = Config::from(cli);
+    // Fake values are drawn from the statistics.
+    config.stats_docs = true;
+
+    let (reader, delimiter) = open_reader(cli, &config, Some(path));
+    config.delimiter = delimiter;
+    let schema = crate::infer(reader, &config)
+        .unwrap_or_else(|e| diagnostic::exit(&e, Some(path), delimiter));
+
+    let seed = seed.unwrap_or_else(|| {
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH);
+        now.map_or(0, |d| d.as_nanos() as u64)
+    });
+    let table = schema.tables.into_iter().next().unwrap_or_default();
+    let csv = crate::fake(&table, rows, seed, &config);
+
+    write_output(cli, &csv);
+}
+
+fn diff(cli: &CLI, old: &PathBuf, new: &PathBuf, format: DiffFormat) {
+    let config = Config::from(cli);
+    let infer = |path| {
+        let (reader, delimiter) = open_reader(cli, &config, Some(path));
+        let schema = crate::infer(reader, &config)
+            .unwrap_or_else(|e| diagnostic::exit(&e, Some(path), delimiter));
+        schema.tables.into_iter().next().unwrap_or_default()
+    };
+
+    let changes = crate::diff(&infer(old), &infer(new));
+
+    match format {
+        DiffFormat::Text => {
+            for change in &changes {
+                println!("{change}");
+            }
+        }
+        DiffFormat::Json => println!("{}", serde_json::to_string_pretty(&changes).unwrap()),
+    }
+
+    if !changes.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+/// Infers the schema of the inputs, or loads it with `--from-schema`. Errors
+/// come with the input they're in, if known.
+fn infer<'a>(
+    cli: &'a CLI,
+    config: &mut Config,
+) -> Result<crate::Schema, (crate::Error, Option<&'a Path>)> {
+    if let Some(path) = &cli.from_schema {
+        let json = std::fs::read_to_string(path).expect("Failed to read the schema.");
+        let mut schema = crate::Schema::from_json(&json, config).map_err(|e| (e, None))?;
+
+        if let ([table], Some(_)) = (schema.tables.as_mut_slice(), &cli.name) {
+            table.name = config.struct_name.clone();
+        }
+        Ok(schema)
+    } else if cli.merge || cli.files.len() > 1 {
+        // Inputs are opened as they're inferred, so an error is in the last one opened.
+        let opened = RefCell::new(vec![]);
+        let open = |path: &'a PathBuf| {
+            let (reader, delimiter) = open_reader(cli, config, Some(path));
+            opened.borrow_mut().push((path.as_path(), delimiter));
+            reader
+        };
+
+        let result = if cli.merge {
+            crate::infer_merged(cli.files.iter().map(open), config)
+        } else {
+            let inputs = cli
+                .files
+                .iter()
+                .map(|path| (get_struct_name(cli, Some(path)), open(path)));
+            crate::infer_all(inputs, config)
+        };
+
+        let opened = opened.into_inner();
+        let mut schema = match result {
+            Ok(schema) => schema,
+            Err(e) => {
+                let (path, delimiter) = *opened.last().expect("Errors come from an input.");
+                config.delimiter = delimiter;
+                return Err((e, Some(path)));
+            }
+        };
+
+        // Code is generated for the first input's delimiter, unless a table has its own.
+        let delimiters = opened.iter().map(|(_, d)| *d).collect::<Vec<_>>();
+        config.delimiter = delimiters.first().copied().unwrap_or(config.delimiter);
+        if cli.merge {
+            if delimiters.iter().any(|d| *d != config.delimiter) {
+                eprintln!(
+                    "warning: the merged inputs use different delimiters, generated code uses '{}'",
+                    config.delimiter.escape_ascii()
+                );
+            }
+        } else {
+            for (table, delimiter) in schema.tables.iter_mut().zip(delimiters) {
+                table.delimiter = (delimiter != config.delimiter).then_some(delimiter);
+            }
+        }
+        Ok(schema)
+    } else {
+        let (reader, delimiter) = open_reader(cli, config, cli.files.first());
+        config.delimiter = delimiter;
+        crate::infer(reader, config).map_err(|e| (e, cli.files.first().map(PathBuf::as_path)))
+    }
+}
+
+/// The generated code for the schema, or the report on it with `--report`.
+fn render(cli: &CLI, config: &Config, schema: &crate::Schema) -> Result<String, crate::Error> {
+    if cli.report {
+        return Ok(crate::report(schema, cli.report_format));
+    }
+
+    let code = generate(cli, config, schema)?;
+    let code = match config.emit.line_comment() {
+        Some(comment) if cli.files.len() > 1 && !cli.merge => {
+            let names = cli
+                .files
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>();
+            format!("{comment} Generated from {}.\n\n{code}", names.join(", "))
+        }
+        _ => code,
+    };
+
+    Ok(match config.emit.line_comment() {
+        Some(comment) if cli.provenance => {
+            // The program's path is left out, as it differs between machines.
+            let args = std::env::args()
+                .enumerate()
+                .map(|(i, arg)| match i {
+                    0 => Path::new(&arg)
+                        .file_name()
+                        .map_or(arg.clone(), |name| name.to_string_lossy().into_owned()),
+                    _ => arg,
+                })
+                .collect::<Vec<_>>();
+            provenance::header(comment, &cli.files, &args) + &code
+        }
+        _ => code,
+    })
+}
+
+/// The generated code, formatted as asked with `--fmt`.
+fn generate(cli: &CLI, config: &Config, schema: &crate::Schema) -> Result<String, crate::Error> {
+    let code = crate::generate(schema, config)?;
+    if cli.fmt != Fmt::Rustfmt || config.emit != Emit::Rust {
+        return Ok(code);
+    }
+
+    // The project's formatting settings are found from where the code goes.
+    let dir = match (&cli.output, &cli.out_dir) {
+        (Some(output), _) => output.parent().map(Path::to_path_buf),
+        (_, Some(dir)) => Some(dir.clone()),
+        _ => None,
+    };
+    let dir = dir
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    Ok(rustfmt::format(&code, &dir).unwrap_or_else(|e| {
+        eprintln!("warning: could not run rustfmt, formatting with prettyplease instead: {e}");
+        code
+    }))
+}
+
+/// Parses the command line, with defaults from a config file. Options given on
+/// the command line take precedence.
+fn parse_args(mut args: Vec<OsString>) -> CLI {
+    let mut matches = CLI::command().get_matches_from(&args);
+    let path = match matches.get_one::<PathBuf>("config") {
+        Some(path) => Some(path.clone()),
+        None => std::env::current_dir()
+            .ok()
+            .and_then(|dir| ConfigFile::find(&dir)),
+    };
+
+    let config_file = match path {
+        Some(path) => {
+            let config_file = ConfigFile::load(&path)
+                .and_then(|file| {
+                    let given = |id: &clap::Id| {
+                        matches.value_source(id.as_str()) == Some(ValueSource::CommandLine)
+                    };
+                    let defaults = file
+                        .args(&CLI::command(), given)
+                        .map_err(|e| format!("{}: {e}", path.display()))?;
+                    args.splice(1..1, defaults);
+                    Ok(file)
+                })
+                .unwrap_or_else(|e| CLI::command().error(ErrorKind::InvalidValue, e).exit());
+
+            matches = CLI::command().get_matches_from(&args);
+            config_file
+        }
+        None => ConfigFile::default(),
+    };
+
+    if let Some(path) = matches.get_one::<PathBuf>("save_config") {
+        config_file
+            .save(path, &CLI::command(), &matches)
+            .expect("Failed to save the config.");
+    }
+
+    let mut cli = CLI::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    cli.config_file = config_file;
+    cli
+}
+
+/// Runs the command line tool on the process's arguments.
+pub fn main() {
+    let (args, is_subcommand) = cargo::args();
+    let mut cli = parse_args(args);
+
+    let level = match cli.verbose {
+        0 => log::LevelFilter::Warn,
+        1 => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    };
+    env_logger::Builder::new()
+        .filter_module("csv2serde", level)
+        .format(|buf, record| match record.level() {
+            log::Level::Warn => writeln!(buf, "warning: {}", record.args()),
+            level => writeln!(buf, "[{level}] {}", record.args()),
+        })
+        .init();
+
+    // As a cargo subcommand, relative output paths are in the project's `src/`.
+    let root = is_subcommand
+        .then(|| {
+            std::env::current_dir()
+                .ok()
+                .as_deref()
+                .and_then(cargo::project_root)
+        })
+        .flatten();
+    if let (Some(root), Some(output)) = (&root, &cli.output) {
+        cli.output = Some(cargo::output_path(root, output));
+    }
+
+    match &cli.command {
+        Some(Command::Diff { old, new, format }) => {
+            diff(&cli, old, new, *format);
+            return;
+        }
+        Some(Command::Fake { file, rows, seed }) => {
+            fake(&cli, file, *rows, *seed);
+            return;
+        }
+        None => {}
+    }
+
+    cli.files = inputs::expand(&cli.files, &cli.dir).expect("Failed to find input files.");
+
+    if cli.files.is_empty() && cli.name.is_none() && cli.from_schema.is_none() {
+        CLI::command()
+            .error(ErrorKind::ValueValidation, "no input files found")
+            .exit();
+    }
+
+    // Answers are read from stdin, so it can't hold the input.
+    let reads_stdin = cli.files.is_empty() && cli.from_schema.is_none();
+    if cli.interactive && (reads_stdin || !io::stdin().is_terminal()) {
+        CLI::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--interactive needs input files, and a terminal to answer from",
+            )
+            .exit();
+    }
+
+    if cli.provenance && cli.emit.line_comment().is_none() {
+        eprintln!("warning: --provenance is ignored for formats without comments");
+    }
+
+    if cli.out_dir.is_some() && cli.emit != Emit::Rust {
+        CLI::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--out-dir only supports Rust output",
+            )
+            .exit();
+    }
+
+    if cli.scaffold.is_some() && cli.emit != Emit::Rust {
+        CLI::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--scaffold only supports Rust output",
+            )
+            .exit();
+    }
+
+    // Only the code generated for the replaced struct goes in its place.
+    if cli.files.len() > 1 && cli.replace_struct.is_some() && !cli.merge {
+        CLI::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--replace-struct can't be used with multiple input files",
+            )
+            .exit();
+    }
+
+    if cli.files.len() > 1 && cli.name.is_some() && !cli.merge {
+        CLI::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--name can't be used with multiple input files",
+            )
+            .exit();
+    }
+
+    if (cli.with_reader || cli.with_iter || cli.with_writer)
+        && cli.delimiter.as_ref().is_some_and(|d| d.len() > 1)
+    {
+        eprintln!(
+            "warning: generated reader and writer functions only support single-byte delimiters"
+        );
+    }
+
+    let mut config = Config::from(&cli);
+
+    let mut schema = infer(&cli, &mut config).unwrap_or_else(|(e, path)| {
+        diagnostic::exit(&e, path, config.delimiter);
+    });
+
+    if cli.interactive {
+        interactive::review(&mut schema, io::stdin().lock(), io::stderr())
+            .expect("Failed to review the columns.");
+    }
+
+    warn_empty_columns(&config, &schema);
+
+    if let Some(path) = &cli.save_schema {
+        std::fs::write(path, schema.to_json()).expect("Failed to save the schema.");
+    }
+
+    if let Some(dir) = &cli.out_dir {
+        let modules = schema
+            .tables
+            .iter()
+            .map(|table| {
+                let schema = crate::Schema {
+                    tables: vec![table.clone()],
+                };
+                let code = generate(&cli, &config, &schema)
+                    .unwrap_or_else(|e| diagnostic::exit(&e, None, config.delimiter));
+                (table.name.to_case(Case::Snake), code)
+            })
+            .collect::<Vec<_>>();
+
+        out_dir::write(dir, &modules, cli.force).expect("Failed to write the modules.");
+        return;
+    }
+
+    let code = render(&cli, &config, &schema)
+        .unwrap_or_else(|e| diagnostic::exit(&e, None, config.delimiter));
+
+    if let Some(path) = &cli.check {
+        if !check::check(path, &code).expect("Failed to read the file to check.") {
+            eprintln!("error: {} is out of date", path.display());
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(dir) = &cli.scaffold {
+        let inputs = if cli.merge || cli.files.is_empty() {
+            vec![scaffold::Input {
+                type_name: config.struct_name.clone(),
+                path: cli.files.first().cloned().filter(|_| cli.files.len() == 1),
+                config: config.clone(),
+            }]
+        } else {
+            schema
+                .tables
+                .iter()
+                .zip(&cli.files)
+                .map(|(table, path)| scaffold::Input {
+                    type_name: table.name.clone(),
+                    path: Some(path.clone()),
+                    config: table.dialect(&config),
+                })
+                .collect()
+        };
+
+        scaffold::write(dir, &code, &inputs).expect("Failed to write the project.");
+        return;
+    }
+
+    let code = if cli.append || cli.replace_struct.is_some() {
+        let output = cli
+            .output
+            .as_ref()
+            .expect("Splicing requires an output file.");
+        let existing = match std::fs::read_to_string(output) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            existing => existing.expect("Failed to read the output file."),
+        };
+
+        let spliced = match &cli.replace_struct {
+            Some(name) if config.emit == Emit::Rust => {
+                splice::replace_struct(&existing, &code, name)
+            }
+            Some(_) => Err("--replace-struct only supports Rust output".to_string()),
+            None => splice::append(&existing, &code, &config.struct_name, config.emit),
+        };
+
+        // The file is kept, with only the generated part replaced.
+        cli.force = true;
+        spliced.unwrap_or_else(|e| {
+            eprintln!("error: {}: {e}", output.display());
+            std::process::exit(1);
+        })
+    } else {
+        code
+    };
+    write_output(&cli, &code);
+
+    if let (true, Some(output)) = (cli.suggest_mod, &cli.output) {
+        let root = root.or_else(|| output.parent().and_then(cargo::project_root));
+        match root.and_then(|root| cargo::mod_declaration(&root, output)) {
+            Some((declaration, file)) => {
+                eprintln!("note: add `{declaration}` to {}", file.display())
+            }
+            None => eprintln!("warning: {} is not a module under src/", output.display()),
+        }
+    }
+
+    if cli.watch {
+        // The output is ours to replace from now on.
+        cli.force = true;
+        watch_inputs(&cli, schema);
+    }
+}
+
+fn write_output(cli: &CLI, code: &str) {
+    let mut destination = WriteDestination::try_from(cli).expect("Failed to write to destination.");
+    destination.write_all(code.as_bytes()).unwrap();
+    destination.flush().unwrap();
+}
+
+/// Writes the output again on every change to the inputs, printing how the
+/// schema changed. Errors are printed without stopping.
+fn watch_inputs(cli: &CLI, mut previous: crate::Schema) {
+    let output = cli
+        .output
+        .as_ref()
+        .expect("Watching requires an output file.");
+    eprintln!("[{}] watching for changes", watch::timestamp());
+
+    watch::watch(&cli.files, || {
+        let mut config = Config::from(cli);
+        let schema = match infer(cli, &mut config) {
+            Ok(schema) => schema,
+            Err((e, path)) => return diagnostic::print(&e, path, config.delimiter),
+        };
+        match render(cli, &config, &schema) {
+            Ok(code) => write_output(cli, &code),
+            Err(e) => return diagnostic::print(&e, None, config.delimiter),
+        }
+
+        let changes = schema
+            .tables
+            .iter()
+            .flat_map(|table| {
+                let old = previous.tables.iter().find(|t| t.name == table.name);
+                crate::diff(old.unwrap_or(&Default::default()), table)
+            })
+            .collect::<Vec<_>>();
+        let summary = match changes.len() {
+            0 => "schema unchanged".to_string(),
+            1 => "1 change".to_string(),
+            n => format!("{n} changes"),
+        };
+        eprintln!(
+            "[{}] wrote {}, {summary}",
+            watch::timestamp(),
+            output.display()
+        );
+        for change in changes {
+            eprintln!("  {change}");
+        }
+
+        previous = schema;
+    })
+    .expect("Failed to watch the input files.");
+}
@@ -0,0 +1,98 @@
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+/// The command line arguments, without the subcommand name cargo passes
+/// first when run as `cargo csv2serde`. Also returns whether it was.
+pub fn args() -> (Vec<OsString>, bool) {
+    split_subcommand(std::env::args_os().collect())
+}
+
+/// Whether `args` were passed to the `cargo-csv2serde` binary, told by its
+/// name rather than the arguments, which may name a file `csv2serde`.
+fn split_subcommand(mut args: Vec<OsString>) -> (Vec<OsString>, bool) {
+    let is_subcommand = args
+        .first()
+        .and_then(|bin| Path::new(bin).file_stem())
+        .is_some_and(|name| name == "cargo-csv2serde");
+    if is_subcommand && args.get(1).is_some_and(|arg| arg == "csv2serde") {
+        args.remove(1);
+    }
+
+    (args, is_subcommand)
+}
+
+/// The directory of the nearest `Cargo.toml` above `dir`, if any.
+pub fn project_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|dir| dir.join("Cargo.toml").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Where a relative output path goes in the project: under `src/`, unless
+/// it already starts there.
+pub fn output_path(root: &Path, output: &Path) -> PathBuf {
+    if output.is_absolute() || output.starts_with("src") {
+        root.join(output)
+    } else {
+        root.join("src").join(output)
+    }
+}
+
+/// The `mod` declaration making the file at `path` part of its crate, and the
+/// file it goes in, if `path` is a module file under `src/`.
+pub fn mod_declaration(root: &Path, path: &Path) -> Option<(String, PathBuf)> {
+    let src = root.join("src");
+    let relative = path.strip_prefix(&src).ok()?;
+    if relative.components().count() != 1 || path.extension()? != "rs" {
+        return None;
+    }
+
+    let name = path.file_stem()?.to_string_lossy();
+    let crate_root = ["lib.rs", "main.rs"]
+        .iter()
+        .map(|file| src.join(file))
+        .find(|file| file.is_file())
+        .unwrap_or_else(|| src.join("main.rs"));
+
+    Some((format!("mod {name};"), crate_root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{output_path, split_subcommand};
+    use std::{ffi::OsString, path::Path};
+
+    #[test]
+    fn output_under_src() {
+        let root = Path::new("/project");
+
+        assert_eq!(
+            output_path(root, Path::new("model.rs")),
+            Path::new("/project/src/model.rs")
+        );
+        assert_eq!(
+            output_path(root, Path::new("src/model.rs")),
+            Path::new("/project/src/model.rs")
+        );
+        assert_eq!(
+            output_path(root, Path::new("/tmp/model.rs")),
+            Path::new("/tmp/model.rs")
+        );
+    }
+
+    #[test]
+    fn subcommand_by_binary_name() {
+        let args = |args: &[&str]| args.iter().map(OsString::from).collect::<Vec<_>>();
+
+        assert_eq!(
+            split_subcommand(args(&["/bin/cargo-csv2serde", "csv2serde", "a.csv"])),
+            (args(&["/bin/cargo-csv2serde", "a.csv"]), true)
+        );
+        assert_eq!(
+            split_subcommand(args(&["csv2serde", "csv2serde"])),
+            (args(&["csv2serde", "csv2serde"]), false)
+        );
+    }
+}
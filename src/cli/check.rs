@@ -0,0 +1,78 @@
+use std::{
+    fs,
+    io::{self, IsTerminal},
+    path::Path,
+};
+
+use similar::{ChangeTag, TextDiff};
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const RESET: &str = "\x1b[0m";
+
+/// Compares the file at `path` with freshly generated code. Returns whether it
+/// is up to date, after printing a diff to stdout if it isn't.
+pub fn check(path: &Path, code: &str) -> io::Result<bool> {
+    let existing = match fs::read_to_string(path) {
+        Ok(existing) => existing,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+
+    if existing == code {
+        return Ok(true);
+    }
+
+    print!(
+        "{}",
+        diff(path, &existing, code, io::stdout().is_terminal())
+    );
+    Ok(false)
+}
+
+/// A unified diff from the existing to the generated code, colored for terminals.
+fn diff(path: &Path, existing: &str, code: &str, color: bool) -> String {
+    let path = path.display().to_string();
+    let diff = TextDiff::from_lines(existing, code);
+    let mut result = format!("--- {path}\n+++ {path} (generated)\n");
+
+    for hunk in diff.unified_diff().context_radius(3).iter_hunks() {
+        result.push_str(&format!("{}\n", hunk.header()));
+
+        for change in hunk.iter_changes() {
+            let (sign, paint) = match change.tag() {
+                ChangeTag::Delete => ("-", RED),
+                ChangeTag::Insert => ("+", GREEN),
+                ChangeTag::Equal => (" ", ""),
+            };
+            let line = change.to_string_lossy();
+            let line = line.trim_end_matches('\n');
+
+            if color && !paint.is_empty() {
+                result.push_str(&format!("{paint}{sign}{line}{RESET}\n"));
+            } else {
+                result.push_str(&format!("{sign}{line}\n"));
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::diff;
+
+    #[test]
+    fn unified() {
+        let existing = "struct A {\n    a: u8,\n}\n";
+        let code = "struct A {\n    a: u16,\n}\n";
+
+        assert_eq!(
+            diff(Path::new("model.rs"), existing, code, false),
+            "--- model.rs\n+++ model.rs (generated)\n@@ -1,3 +1,3 @@\n struct A {\n-    a: u8,\n+    a: u16,\n }\n"
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use std::{
+    io::{self, BufReader},
+    path::Path,
+};
+
+use bzip2::read::MultiBzDecoder;
+use flate2::read::MultiGzDecoder;
+
+#[derive(Copy, Clone, Debug, PartialEq, clap::ValueEnum)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zstd,
+    Bzip2,
+}
+
+impl Compression {
+    /// Guesses the compression from the file extension, e.g. `orders.csv.gz`.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        let extension = path.as_ref().extension().and_then(|e| e.to_str());
+        match extension {
+            Some("gz") => Compression::Gzip,
+            Some("zst") => Compression::Zstd,
+            Some("bz2") => Compression::Bzip2,
+            _ => Compression::None,
+        }
+    }
+}
+
+pub enum Decoder<R: io::Read> {
+    Plain(R),
+    Gzip(MultiGzDecoder<R>),
+    Zstd(zstd::Decoder<'static, BufReader<R>>),
+    Bzip2(MultiBzDecoder<R>),
+}
+
+impl<R: io::Read> Decoder<R> {
+    pub fn new(inner: R, compression: Compression) -> io::Result<Self> {
+        let decoder = match compression {
+            Compression::None => Decoder::Plain(inner),
+            Compression::Gzip => Decoder::Gzip(MultiGzDecoder::new(inner)),
+            Compression::Zstd => Decoder::Zstd(zstd::Decoder::new(inner)?),
+            Compression::Bzip2 => Decoder::Bzip2(MultiBzDecoder::new(inner)),
+        };
+
+        Ok(decoder)
+    }
+}
+
+impl<R: io::Read> io::Read for Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Decoder::Plain(r) => r.read(buf),
+            Decoder::Gzip(r) => r.read(buf),
+            Decoder::Zstd(r) => r.read(buf),
+            Decoder::Bzip2(r) => r.read(buf),
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use std::{
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use clap::{parser::ValueSource, ArgAction, ArgMatches, Command, Id};
+use toml::Value;
+
+pub const FILE_NAME: &str = "csv2serde.toml";
+
+/// Defaults read from a `csv2serde.toml`: values of long options, keyed by
+/// their names, and a `[names]` table of struct names by input path, relative
+/// to the file.
+#[derive(Debug, Default)]
+pub struct ConfigFile {
+    options: toml::Table,
+    names: Vec<(PathBuf, String)>,
+    dir: PathBuf,
+}
+
+impl ConfigFile {
+    /// The nearest config file in `dir` or above it.
+    pub fn find(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors()
+            .map(|dir| dir.join(FILE_NAME))
+            .find(|path| path.is_file())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("could not read {}: {e}", path.display()))?;
+        let mut file =
+            Self::parse(&text).map_err(|e| format!("invalid {}: {e}", path.display()))?;
+        file.dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+        Ok(file)
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let mut options = text.parse::<toml::Table>().map_err(|e| e.to_string())?;
+
+        let names = match options.remove("names") {
+            Some(Value::Table(names)) => names
+                .into_iter()
+                .map(|(path, name)| match name {
+                    Value::String(name) => Ok((PathBuf::from(path), name)),
+                    _ => Err(format!("the struct name for '{path}' should be a string")),
+                })
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err("`names` should be a table of struct names by path".into()),
+            None => vec![],
+        };
+
+        Ok(ConfigFile {
+            options,
+            names,
+            dir: PathBuf::new(),
+        })
+    }
+
+    /// The options as arguments to `command`, leaving out those `given` on
+    /// the command line, which take precedence.
+    pub fn args(
+        &self,
+        command: &Command,
+        given: impl Fn(&Id) -> bool,
+    ) -> Result<Vec<OsString>, String> {
+        let mut args = vec![];
+
+        for (key, value) in &self.options {
+            let arg = command
+                .get_arguments()
+                .find(|arg| arg.get_long() == Some(key))
+                .ok_or_else(|| format!("unknown option `{key}`"))?;
+            if given(arg.get_id()) {
+                continue;
+            }
+
+            let flag = format!("--{key}");
+            match (arg.get_action(), value) {
+                (ArgAction::SetTrue, Value::Boolean(set)) => {
+                    args.extend(set.then(|| flag.clone()));
+                }
+                (ArgAction::Count, Value::Integer(count)) => {
+                    args.extend((0..*count).map(|_| flag.clone()));
+                }
+                (_, Value::Array(values)) => {
+                    for value in values {
+                        args.push(format!("{flag}={}", scalar(key, value)?));
+                    }
+                }
+                (_, Value::Table(entries)) => {
+                    for (name, value) in entries {
+                        args.push(format!("{flag}={name}={}", scalar(key, value)?));
+                    }
+                }
+                (_, value) => args.push(format!("{flag}={}", scalar(key, value)?)),
+            }
+        }
+
+        Ok(args.into_iter().map(OsString::from).collect())
+    }
+
+    /// Writes the options set in `matches`, other than those choosing config
+    /// files, with the struct names of this file.
+    pub fn save(&self, path: &Path, command: &Command, matches: &ArgMatches) -> io::Result<()> {
+        let mut table = options(command, matches);
+
+        // Names are relative to the file they're in.
+        let same_dir = fs::canonicalize(path.parent().unwrap_or(Path::new("")).join("."))
+            .ok()
+            .zip(fs::canonicalize(self.dir.join(".")).ok())
+            .is_some_and(|(a, b)| a == b);
+        if !self.names.is_empty() {
+            let names = self
+                .names
+                .iter()
+                .map(|(named, name)| {
+                    let named = if same_dir {
+                        named.clone()
+                    } else {
+                        self.dir.join(named)
+                    };
+                    (
+                        named.to_string_lossy().into_owned(),
+                        Value::from(name.as_str()),
+                    )
+                })
+                .collect::<toml::Table>();
+            table.insert("names".to_string(), Value::Table(names));
+        }
+
+        let text = toml::to_string(&table).map_err(io::Error::other)?;
+        fs::write(path, text)
+    }
+
+    /// The struct name configured for the input at `path`. Inputs that don't
+    /// exist as files, like URLs, are matched by their trailing components.
+    pub fn name(&self, path: &Path) -> Option<&str> {
+        let canonical = fs::canonicalize(path).ok();
+
+        self.names
+            .iter()
+            .find(|(named, _)| match &canonical {
+                Some(canonical) => {
+                    fs::canonicalize(self.dir.join(named)).is_ok_and(|named| named == *canonical)
+                }
+                None => path.ends_with(named),
+            })
+            .map(|(_, name)| name.as_str())
+    }
+}
+
+/// The options set in `matches`, by long name, as they were given.
+fn options(command: &Command, matches: &ArgMatches) -> toml::Table {
+    let mut table = toml::Table::new();
+
+    for arg in command.get_arguments() {
+        let (Some(key), id) = (arg.get_long(), arg.get_id().as_str()) else {
+            continue;
+        };
+        let set = matches!(
+            matches.value_source(id),
+            Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+        );
+        if !set || ["config", "save-config"].contains(&key) {
+            continue;
+        }
+
+        let raw = || {
+            matches
+                .get_raw(id)
+                .into_iter()
+                .flatten()
+                .map(|value| typed(&value.to_string_lossy()))
+        };
+        let value = match arg.get_action() {
+            ArgAction::SetTrue => Value::Boolean(true),
+            ArgAction::Count => Value::Integer(matches.get_count(id).into()),
+            ArgAction::Append => Value::Array(raw().collect()),
+            _ => match raw().next() {
+                Some(value) => value,
+                None => continue,
+            },
+        };
+        table.insert(key.to_string(), value);
+    }
+
+    table
+}
+
+/// A value given on the command line, as a boolean or integer where it reads
+/// as one, to be given again as the same text.
+fn typed(value: &str) -> Value {
+    match (value.parse::<bool>(), value.parse::<i64>()) {
+        (Ok(b), _) => Value::Boolean(b),
+        (_, Ok(n)) if n.to_string() == value => Value::Integer(n),
+        _ => Value::from(value),
+    }
+}
+
+fn scalar(key: &str, value: &Value) -> Result<String, String> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Integer(n) => Ok(n.to_string()),
+        Value::Float(n) => Ok(n.to_string()),
+        Value::Boolean(b) => Ok(b.to_string()),
+        _ => Err(format!(
+            "values of `{key}` should be strings, numbers or booleans"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{options, ConfigFile};
+    use crate::cli::CLI;
+    use clap::CommandFactory;
+    use std::path::Path;
+
+    #[test]
+    fn args() {
+        let file = ConfigFile::parse(
+            r#"
+            delimiter = ";"
+            derive = ["Clone", "Serialize"]
+            null = ["NA"]
+            borrow = true
+            with-imports = false
+
+            [type]
+            id = "u64"
+
+            [names]
+            "data/orders.csv" = "Order"
+            "#,
+        )
+        .unwrap();
+
+        let args = file
+            .args(&CLI::command(), |id| id == "delimiter")
+            .unwrap()
+            .into_iter()
+            .map(|arg| arg.into_string().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            args,
+            [
+                "--borrow",
+                "--derive=Clone",
+                "--derive=Serialize",
+                "--null=NA",
+                "--type=id=u64",
+                "--with-imports=false",
+            ]
+        );
+
+        assert_eq!(
+            file.name(Path::new("https://example.com/data/orders.csv")),
+            Some("Order")
+        );
+        assert_eq!(file.name(Path::new("orders.csv")), None);
+    }
+
+    #[test]
+    fn save_options() {
+        let matches = CLI::command().get_matches_from([
+            "csv2serde",
+            "in.csv",
+            "--derive=Clone,Serialize",
+            "-l",
+            "100",
+            "--borrow",
+            "--with-imports=false",
+            "--save-config=out.toml",
+        ]);
+
+        assert_eq!(
+            toml::to_string(&options(&CLI::command(), &matches)).unwrap(),
+            "borrow = true\nderive = [\"Clone\", \"Serialize\"]\nlines = 100\nwith-imports = false\n"
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use std::path::Path;
+
+use crate::Error;
+
+/// Characters of the offending row shown around the offending value.
+const CONTEXT: usize = 40;
+
+/// Prints `error` and exits, pointing at the offending record the way rustc
+/// points at code.
+pub fn exit(error: &Error, path: Option<&Path>, delimiter: u8) -> ! {
+    print(error, path, delimiter);
+    std::process::exit(1);
+}
+
+/// Prints `error` like [`exit`], without exiting.
+pub fn print(error: &Error, path: Option<&Path>, delimiter: u8) {
+    eprint!("{}", render(error, path, delimiter));
+}
+
+fn render(error: &Error, path: Option<&Path>, delimiter: u8) -> String {
+    let mut result = format!("error: {error}\n");
+    let Some(context) = error.record_context() else {
+        return result;
+    };
+
+    let line = context.line.to_string();
+    let gutter = " ".repeat(line.len());
+    let path = path.map_or("<stdin>".to_string(), |p| p.display().to_string());
+    result.push_str(&format!("{gutter}--> {path}:{line}\n{gutter} |\n"));
+
+    // The row as it was read, cut down to the offending value and its surroundings.
+    let separator = char::from(delimiter).to_string();
+    let (before, value, after) = match &context.column {
+        Some((index, _)) => (
+            context.fields[..*index]
+                .iter()
+                .map(|f| f.clone() + &separator)
+                .collect(),
+            context.value().unwrap_or_default().to_string(),
+            context.fields[index + 1..]
+                .iter()
+                .map(|f| separator.clone() + f)
+                .collect(),
+        ),
+        None => (
+            String::new(),
+            String::new(),
+            context.fields.join(&separator),
+        ),
+    };
+    let before = elide_start(&before);
+    let after = elide_end(&after);
+    result.push_str(&format!("{line} | {before}{value}{after}\n"));
+
+    if let Some((_, header)) = &context.column {
+        let offset = " ".repeat(before.chars().count());
+        let marker = "^".repeat(value.chars().count().max(1));
+        result.push_str(&format!("{gutter} | {offset}{marker} column '{header}'\n"));
+    }
+
+    result
+}
+
+fn elide_start(s: &str) -> String {
+    match s.char_indices().rev().nth(CONTEXT) {
+        Some((i, _)) => format!("...{}", &s[i..]),
+        None => s.to_string(),
+    }
+}
+
+fn elide_end(s: &str) -> String {
+    match s.char_indices().nth(CONTEXT) {
+        Some((i, _)) => format!("{}...", &s[..i]),
+        None => s.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render;
+    use crate::{run_on_str, Config};
+    use std::path::Path;
+
+    #[test]
+    fn unequal_lengths() {
+        let error = run_on_str("a,b\n1,2\n3,4,5\n", &Config::default()).unwrap_err();
+
+        assert_eq!(
+            render(&error, Some(Path::new("data.csv")), b','),
+            format!(
+                "error: {error}\n \
+                 --> data.csv:3\n  \
+                 |\n\
+                 3 | 3,4,5\n"
+            )
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use super::{compression::Compression, reader_source};
+
+/// Expands glob patterns among `files`, for shells which don't do it for us,
+/// and appends every CSV/TSV file found under `dirs`.
+pub fn expand(files: &[PathBuf], dirs: &[PathBuf]) -> io::Result<Vec<PathBuf>> {
+    let mut result = vec![];
+
+    for file in files {
+        let pattern = file.to_string_lossy();
+        if !pattern.contains(['*', '?', '[']) || reader_source::is_url(file) {
+            result.push(file.clone());
+            continue;
+        }
+
+        let paths = glob::glob(&pattern).map_err(|e| io::Error::other(e.to_string()))?;
+        for path in paths {
+            result.push(path.map_err(io::Error::from)?);
+        }
+    }
+
+    for dir in dirs {
+        let mut found = vec![];
+        walk(dir, &mut found)?;
+        found.sort();
+        result.extend(found);
+    }
+
+    Ok(result)
+}
+
+fn walk(dir: &Path, found: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            walk(&path, found)?;
+        } else if is_csv(&path) {
+            found.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+fn is_csv(path: &Path) -> bool {
+    let path = match Compression::from_path(path) {
+        Compression::None => path.to_path_buf(),
+        _ => path.with_extension(""),
+    };
+
+    let extension = path.extension().and_then(|e| e.to_str());
+    matches!(extension, Some("csv" | "tsv"))
+}
@@ -0,0 +1,159 @@
+use std::io::{self, BufRead, Write};
+
+use crate::{Field, Schema};
+
+const HELP: &str = "Enter to accept, `r NAME` to rename, `t TYPE` to retype, `o` to toggle optional, `q` to accept the rest";
+
+/// A change to a column, made to its field in every group.
+#[derive(Debug, PartialEq)]
+enum Edit {
+    Rename(String),
+    Retype(String),
+    Optional(bool),
+}
+
+#[derive(Debug, PartialEq)]
+enum Answer {
+    Accept,
+    Edit(Edit),
+    ToggleOptional,
+    AcceptRest,
+    Invalid(String),
+}
+
+/// Walks through the columns of each table, showing what was inferred for
+/// them, and applies the changes answered on `input`.
+pub fn review(schema: &mut Schema, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    let mut lines = input.lines();
+    writeln!(output, "{HELP}")?;
+
+    for table in &mut schema.tables {
+        let fields = table.fields();
+
+        for (i, field) in fields.iter().enumerate() {
+            let mut field = field.clone();
+            let mut edits = vec![];
+
+            loop {
+                describe(&mut output, &field, i + 1, fields.len())?;
+                write!(output, "> ")?;
+                output.flush()?;
+
+                let answer = match lines.next().transpose()? {
+                    Some(line) => parse_answer(&line),
+                    None => Answer::AcceptRest,
+                };
+                let edit = match answer {
+                    Answer::Accept => break,
+                    Answer::AcceptRest => {
+                        apply(table, &field.raw_name, &edits);
+                        return Ok(());
+                    }
+                    Answer::Invalid(message) => {
+                        writeln!(output, "{message}\n{HELP}")?;
+                        continue;
+                    }
+                    Answer::ToggleOptional => Edit::Optional(!field.is_option()),
+                    Answer::Edit(edit) => edit,
+                };
+
+                field = edited(field, &edit);
+                edits.push(edit);
+            }
+
+            apply(table, &field.raw_name, &edits);
+        }
+    }
+
+    Ok(())
+}
+
+fn describe(output: &mut impl Write, field: &Field, index: usize, count: usize) -> io::Result<()> {
+    let samples = field
+        .samples()
+        .iter()
+        .map(|s| format!("{s:?}"))
+        .collect::<Vec<_>>();
+
+    writeln!(
+        output,
+        "[{index}/{count}] {:?} -> {}: {}",
+        field.raw_name,
+        field.name,
+        field.type_name()
+    )?;
+    writeln!(
+        output,
+        "      samples: {}, nulls: {} of {}",
+        samples.join(", "),
+        field.empty_cells(),
+        field.cells()
+    )
+}
+
+fn parse_answer(line: &str) -> Answer {
+    let line = line.trim();
+    let (command, argument) = line.split_once(' ').unwrap_or((line, ""));
+    let argument = argument.trim();
+
+    match (command, argument) {
+        ("", _) => Answer::Accept,
+        ("o", "") => Answer::ToggleOptional,
+        ("q", "") => Answer::AcceptRest,
+        ("r", name) if !name.is_empty() => Answer::Edit(Edit::Rename(name.to_string())),
+        ("t", type_name) => match syn::parse_str::<syn::Type>(type_name) {
+            Ok(_) => Answer::Edit(Edit::Retype(type_name.to_string())),
+            Err(e) => Answer::Invalid(format!("invalid type '{type_name}': {e}")),
+        },
+        _ => Answer::Invalid(format!("unknown answer '{line}'")),
+    }
+}
+
+fn edited(field: Field, edit: &Edit) -> Field {
+    match edit {
+        Edit::Rename(name) => field.renamed(name),
+        Edit::Retype(type_name) => {
+            let mut field = field;
+            field.set_type_override(Some(type_name.clone()));
+            field
+        }
+        Edit::Optional(optional) => {
+            let mut field = field;
+            field.set_optional_override(Some(*optional));
+            field
+        }
+    }
+}
+
+/// Makes the edits to the column's field in every group of the table.
+fn apply(table: &mut crate::Table, raw_name: &str, edits: &[Edit]) {
+    for (_, fields) in &mut table.groups {
+        for field in fields.iter_mut().filter(|f| f.raw_name == raw_name) {
+            for edit in edits {
+                *field = edited(field.clone(), edit);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::review;
+    use crate::Config;
+
+    #[test]
+    fn edits() {
+        let csv = "id,score\n1,2\n2,\n";
+        let config = Config::default();
+        let mut schema =
+            crate::infer(config.reader_builder().from_reader(csv.as_bytes()), &config).unwrap();
+
+        let answers = "t u64\nr identifier\n\nx\no\n";
+        review(&mut schema, answers.as_bytes(), std::io::sink()).unwrap();
+
+        let fields = schema.tables[0].fields();
+        assert_eq!(fields[0].name, "identifier");
+        assert_eq!(fields[0].type_name(), "u64");
+        assert_eq!(fields[1].type_name(), "u8");
+    }
+}
@@ -0,0 +1,58 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::Path,
+};
+
+/// Writes each module's code into `dir` as `<module>.rs`, with a `mod.rs`
+/// declaring them and re-exporting their items. Existing files are only
+/// replaced with `force`.
+pub fn write(dir: &Path, modules: &[(String, String)], force: bool) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+
+    for (module, code) in modules {
+        create(&dir.join(format!("{module}.rs")), code, force)?;
+    }
+    create(&dir.join("mod.rs"), &mod_rs(modules), force)
+}
+
+fn create(path: &Path, content: &str, force: bool) -> io::Result<()> {
+    File::options()
+        .write(true)
+        .create(true)
+        .create_new(!force)
+        .truncate(true)
+        .open(path)
+        .and_then(|mut f| f.write_all(content.as_bytes()))
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))
+}
+
+fn mod_rs(modules: &[(String, String)]) -> String {
+    let mut result = String::new();
+    for (module, _) in modules {
+        result.push_str(&format!("mod {module};\n"));
+    }
+    result.push('\n');
+    for (module, _) in modules {
+        result.push_str(&format!("pub use {module}::*;\n"));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mod_rs;
+
+    #[test]
+    fn declares_and_reexports() {
+        let modules = [
+            ("orders".to_string(), String::new()),
+            ("order_lines".to_string(), String::new()),
+        ];
+
+        assert_eq!(
+            mod_rs(&modules),
+            "mod orders;\nmod order_lines;\n\npub use orders::*;\npub use order_lines::*;\n"
+        );
+    }
+}
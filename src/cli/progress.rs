@@ -0,0 +1,91 @@
+use std::{
+    fmt::Write,
+    io,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use indicatif::{ProgressBar, ProgressFinish, ProgressState, ProgressStyle};
+
+/// A progress bar over the bytes of an input of known length, also showing
+/// how many rows were read and how fast.
+#[derive(Clone)]
+pub struct Progress {
+    bar: ProgressBar,
+    rows: Arc<AtomicU64>,
+}
+
+impl Progress {
+    pub fn new(len: u64) -> Self {
+        let rows = Arc::new(AtomicU64::new(0));
+        let (count, rate) = (rows.clone(), rows.clone());
+
+        let style = ProgressStyle::with_template(
+            "{wide_bar} {bytes}/{total_bytes}, {rows} rows ({rows_per_sec}/s), ETA {eta}",
+        )
+        .expect("The progress template is valid.")
+        .with_key("rows", move |_: &ProgressState, w: &mut dyn Write| {
+            write!(w, "{}", count.load(Ordering::Relaxed)).unwrap()
+        })
+        .with_key(
+            "rows_per_sec",
+            move |state: &ProgressState, w: &mut dyn Write| {
+                let seconds = state.elapsed().as_secs_f64().max(f64::EPSILON);
+                write!(w, "{:.0}", rate.load(Ordering::Relaxed) as f64 / seconds).unwrap()
+            },
+        );
+
+        let bar = ProgressBar::new(len)
+            .with_style(style)
+            .with_finish(ProgressFinish::AndClear);
+
+        Progress { bar, rows }
+    }
+
+    /// Advances the bar by the bytes read from `inner`, which should be the
+    /// input as stored, e.g. before decompressing it.
+    pub fn bytes<R>(&self, inner: R) -> CountBytes<R> {
+        CountBytes {
+            inner,
+            bar: self.bar.clone(),
+        }
+    }
+
+    /// Counts the lines read from `inner`, which should be the decoded input.
+    /// Quoted line breaks are counted as rows too.
+    pub fn rows<R>(&self, inner: R) -> CountRows<R> {
+        CountRows {
+            inner,
+            rows: self.rows.clone(),
+        }
+    }
+}
+
+pub struct CountBytes<R> {
+    inner: R,
+    bar: ProgressBar,
+}
+
+impl<R: io::Read> io::Read for CountBytes<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.bar.inc(n as u64);
+        Ok(n)
+    }
+}
+
+pub struct CountRows<R> {
+    inner: R,
+    rows: Arc<AtomicU64>,
+}
+
+impl<R: io::Read> io::Read for CountRows<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        let lines = buf[..n].iter().filter(|&&b| b == b'\n').count();
+        self.rows.fetch_add(lines as u64, Ordering::Relaxed);
+        Ok(n)
+    }
+}
@@ -0,0 +1,104 @@
+use std::{
+    fs::File,
+    io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use sha2::{Digest, Sha256};
+
+/// Comment lines recording how the output was generated: by which version,
+/// when, from which inputs, and with which command.
+pub fn header(comment: &str, files: &[PathBuf], args: &[String]) -> String {
+    let mut lines = vec![format!(
+        "Generated by csv2serde {} at {}.",
+        env!("CARGO_PKG_VERSION"),
+        timestamp(SystemTime::now())
+    )];
+
+    if files.is_empty() {
+        lines.push("Source: <stdin>".to_string());
+    }
+    for path in files {
+        lines.push(match sha256(path) {
+            Ok(hash) => format!("Source: {} (sha256 {hash})", path.display()),
+            Err(_) => format!("Source: {}", path.display()),
+        });
+    }
+
+    let args = args.iter().map(|arg| quote(arg)).collect::<Vec<_>>();
+    lines.push(format!("Command: {}", args.join(" ")));
+
+    lines
+        .iter()
+        .map(|line| format!("{comment} {line}\n"))
+        .collect::<String>()
+        + "\n"
+}
+
+fn sha256(path: &PathBuf) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// The argument as it would be typed into a POSIX shell.
+fn quote(arg: &str) -> String {
+    let plain = |c: char| c.is_ascii_alphanumeric() || "-_./=:,@+%".contains(c);
+    if !arg.is_empty() && arg.chars().all(plain) {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+/// The time in UTC, in ISO 8601, like `2024-03-01T12:30:00Z`.
+fn timestamp(time: SystemTime) -> String {
+    let seconds = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (days, seconds) = (seconds / 86400, seconds % 86400);
+
+    // Converts days since 1970 to a civil date, after Howard Hinnant's `civil_from_days`.
+    let days = days as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{quote, timestamp};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn timestamps() {
+        let at = |seconds| timestamp(UNIX_EPOCH + Duration::from_secs(seconds));
+
+        assert_eq!(at(0), "1970-01-01T00:00:00Z");
+        assert_eq!(at(951_827_696), "2000-02-29T12:34:56Z");
+        assert_eq!(at(1_709_251_199), "2024-02-29T23:59:59Z");
+    }
+
+    #[test]
+    fn quoting() {
+        assert_eq!(quote("--type=id=u64"), "--type=id=u64");
+        assert_eq!(quote("Order ID"), "'Order ID'");
+        assert_eq!(quote("it's"), r"'it'\''s'");
+    }
+}
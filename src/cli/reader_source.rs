@@ -0,0 +1,109 @@
+use std::{
+    fs::File,
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::cli::CLI;
+
+pub enum ReaderSource {
+    File(File),
+    Stdin,
+    #[cfg(feature = "remote")]
+    Remote(Box<dyn io::Read + Send + Sync>),
+    #[cfg(feature = "xlsx")]
+    Workbook(io::Cursor<Vec<u8>>),
+    #[cfg(feature = "mmap")]
+    Mapped(io::Cursor<memmap2::Mmap>),
+}
+
+impl io::Read for ReaderSource {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // No need to buffer manually; csv::Reader buffers for us.
+        match self {
+            ReaderSource::Stdin => io::stdin().read(buf),
+            ReaderSource::File(f) => f.read(buf),
+            #[cfg(feature = "remote")]
+            ReaderSource::Remote(r) => r.read(buf),
+            #[cfg(feature = "xlsx")]
+            ReaderSource::Workbook(c) => c.read(buf),
+            #[cfg(feature = "mmap")]
+            ReaderSource::Mapped(c) => c.read(buf),
+        }
+    }
+}
+
+impl ReaderSource {
+    pub fn open(cli: &CLI, path: Option<&PathBuf>) -> io::Result<Self> {
+        match path {
+            Some(path) if is_url(path) => open_url(path),
+            Some(path) if is_workbook(path) => open_workbook(cli, path),
+            Some(path) if cli.mmap => open_mapped(path),
+            Some(path) => {
+                let file = File::open(path)?;
+                Ok(ReaderSource::File(file))
+            }
+            None => Ok(ReaderSource::Stdin),
+        }
+    }
+}
+
+pub fn is_url<P: AsRef<Path>>(path: P) -> bool {
+    let path = path.as_ref().to_string_lossy();
+    path.starts_with("http://") || path.starts_with("https://")
+}
+
+pub fn is_workbook<P: AsRef<Path>>(path: P) -> bool {
+    let extension = path.as_ref().extension().and_then(|e| e.to_str());
+    matches!(extension, Some("xlsx" | "xlsm" | "xls" | "ods"))
+}
+
+#[cfg(feature = "remote")]
+fn open_url(url: &Path) -> io::Result<ReaderSource> {
+    // ureq transparently handles `Content-Encoding: gzip`.
+    let response = ureq::get(&url.to_string_lossy())
+        .call()
+        .map_err(io::Error::other)?;
+
+    Ok(ReaderSource::Remote(Box::new(response.into_reader())))
+}
+
+#[cfg(not(feature = "remote"))]
+fn open_url(url: &Path) -> io::Result<ReaderSource> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("Reading '{}' requires the `remote` feature.", url.display()),
+    ))
+}
+
+#[cfg(feature = "mmap")]
+fn open_mapped(path: &Path) -> io::Result<ReaderSource> {
+    let file = File::open(path)?;
+    // SAFETY: The file must not change while it's mapped, as for any input
+    // read while it's being written.
+    let map = unsafe { memmap2::Mmap::map(&file)? };
+    Ok(ReaderSource::Mapped(io::Cursor::new(map)))
+}
+
+#[cfg(not(feature = "mmap"))]
+fn open_mapped(path: &Path) -> io::Result<ReaderSource> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("Mapping '{}' requires the `mmap` feature.", path.display()),
+    ))
+}
+
+#[cfg(feature = "xlsx")]
+fn open_workbook(cli: &CLI, path: &Path) -> io::Result<ReaderSource> {
+    let delimiter = crate::cli::get_delimiter_byte(cli).unwrap_or(b',');
+    let csv = super::workbook::sheet_to_csv(path, cli.sheet.as_deref(), delimiter)?;
+    Ok(ReaderSource::Workbook(io::Cursor::new(csv)))
+}
+
+#[cfg(not(feature = "xlsx"))]
+fn open_workbook(_cli: &CLI, path: &Path) -> io::Result<ReaderSource> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("Reading '{}' requires the `xlsx` feature.", path.display()),
+    ))
+}
@@ -0,0 +1,140 @@
+use std::io;
+
+/// ASCII unit separator, which multi-byte delimiters are rewritten to, as the
+/// csv crate only supports single-byte delimiters.
+pub const REPLACEMENT: u8 = 0x1F;
+
+/// Rewrites every occurrence of a multi-byte delimiter, e.g. `||`, into [`REPLACEMENT`],
+/// except inside quoted fields. An empty delimiter passes the input through unchanged.
+pub struct ReplaceDelimiter<R> {
+    inner: R,
+    delimiter: Vec<u8>,
+    quote: u8,
+    escape: Option<u8>,
+    // Whether the scanned bytes end inside a quoted field.
+    quoted: bool,
+    // Bytes read from `inner`, not yet scanned.
+    input: Vec<u8>,
+    // Scanned bytes, not yet returned.
+    output: Vec<u8>,
+    position: usize,
+    eof: bool,
+}
+
+impl<R> ReplaceDelimiter<R> {
+    pub fn new(inner: R, delimiter: &[u8], quote: u8, escape: Option<u8>) -> Self {
+        ReplaceDelimiter {
+            inner,
+            delimiter: delimiter.to_vec(),
+            quote,
+            escape,
+            quoted: false,
+            input: vec![],
+            output: vec![],
+            position: 0,
+            eof: false,
+        }
+    }
+}
+
+impl<R: io::Read> ReplaceDelimiter<R> {
+    fn fill(&mut self) -> io::Result<()> {
+        let mut chunk = [0; 8192];
+        let n = self.inner.read(&mut chunk)?;
+        self.eof = n == 0;
+        self.input.extend_from_slice(&chunk[..n]);
+
+        self.output.clear();
+        self.position = 0;
+
+        let mut i = 0;
+        while i < self.input.len() {
+            let rest = &self.input[i..];
+
+            if self.quoted && Some(rest[0]) == self.escape {
+                if rest.len() == 1 && !self.eof {
+                    // The escaped byte is yet to be read.
+                    break;
+                }
+                let n = rest.len().min(2);
+                self.output.extend_from_slice(&rest[..n]);
+                i += n;
+            } else if rest[0] == self.quote {
+                // Doubled quotes inside a field toggle twice, leaving it quoted.
+                self.quoted = !self.quoted;
+                self.output.push(rest[0]);
+                i += 1;
+            } else if self.quoted {
+                self.output.push(rest[0]);
+                i += 1;
+            } else if rest.starts_with(&self.delimiter) {
+                self.output.push(REPLACEMENT);
+                i += self.delimiter.len();
+            } else if !self.eof && self.delimiter.starts_with(rest) {
+                // Possibly a delimiter cut in half; wait for more input.
+                break;
+            } else {
+                self.output.push(self.input[i]);
+                i += 1;
+            }
+        }
+
+        self.input.drain(..i);
+        Ok(())
+    }
+}
+
+impl<R: io::Read> io::Read for ReplaceDelimiter<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.delimiter.is_empty() {
+            return self.inner.read(buf);
+        }
+
+        while self.position == self.output.len() {
+            if self.eof {
+                return Ok(0);
+            }
+            self.fill()?;
+        }
+
+        let n = buf.len().min(self.output.len() - self.position);
+        buf[..n].copy_from_slice(&self.output[self.position..self.position + n]);
+        self.position += n;
+
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReplaceDelimiter, REPLACEMENT};
+    use std::io::Read;
+
+    #[test]
+    fn replace_delimiter() {
+        let mut output = vec![];
+        ReplaceDelimiter::new(&b"a||b|c||\n1||2"[..], b"||", b'"', None)
+            .read_to_end(&mut output)
+            .unwrap();
+
+        let r = REPLACEMENT;
+        assert_eq!(
+            output,
+            [b'a', r, b'b', b'|', b'c', r, b'\n', b'1', r, b'2'].to_vec()
+        );
+    }
+
+    #[test]
+    fn keep_quoted_delimiters() {
+        let mut output = vec![];
+        ReplaceDelimiter::new(&br#"a||"b||""c""||\"||"||d"#[..], b"||", b'"', Some(b'\\'))
+            .read_to_end(&mut output)
+            .unwrap();
+
+        let r = REPLACEMENT;
+        let mut expected = vec![b'a', r];
+        expected.extend_from_slice(br#""b||""c""||\"||""#);
+        expected.extend_from_slice(&[r, b'd']);
+        assert_eq!(output, expected);
+    }
+}
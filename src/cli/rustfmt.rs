@@ -0,0 +1,42 @@
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+/// Formats `code` with rustfmt, following the `rustfmt.toml` in `dir` or
+/// above it, if any.
+pub fn format(code: &str, dir: &Path) -> io::Result<String> {
+    let mut command = Command::new("rustfmt");
+    command.args(["--emit", "stdout", "--edition", "2021"]);
+    if let Some(config) = find_config(dir) {
+        command.arg("--config-path").arg(config);
+    }
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // rustfmt reads all of its input before writing anything, so this can't deadlock.
+    child
+        .stdin
+        .take()
+        .expect("rustfmt's stdin is piped.")
+        .write_all(code.as_bytes())?;
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(message.trim().to_string()));
+    }
+
+    String::from_utf8(output.stdout).map_err(io::Error::other)
+}
+
+fn find_config(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .flat_map(|dir| ["rustfmt.toml", ".rustfmt.toml"].map(|name| dir.join(name)))
+        .find(|path| path.is_file())
+}
@@ -0,0 +1,153 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{codegen::readers, Config};
+use convert_case::{Case, Casing};
+use quote::{format_ident, quote};
+
+/// A type generated by this run, and the input it was generated from.
+pub struct Input {
+    pub type_name: String,
+    pub path: Option<PathBuf>,
+    /// Settings the input is read with.
+    pub config: Config,
+}
+
+/// Writes a cargo project into `dir`, with the generated code as `src/model.rs`
+/// and a `src/main.rs` counting the rows of each input. Existing files are
+/// left alone, and reported as an error.
+pub fn write(dir: &Path, code: &str, inputs: &[Input]) -> io::Result<()> {
+    let package_name = dir
+        .canonicalize()
+        .ok()
+        .or_else(|| Some(dir.to_path_buf()))
+        .and_then(|dir| {
+            dir.file_name()
+                .map(|n| n.to_string_lossy().to_case(Case::Kebab))
+        })
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "csv-model".to_string());
+
+    fs::create_dir_all(dir.join("src"))?;
+
+    create(&dir.join("Cargo.toml"), &cargo_toml(&package_name))?;
+    create(&dir.join("src/model.rs"), code)?;
+    create(&dir.join("src/main.rs"), &main_rs(inputs))?;
+
+    Ok(())
+}
+
+fn create(path: &Path, content: &str) -> io::Result<()> {
+    File::options()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut f| f.write_all(content.as_bytes()))
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))
+}
+
+fn cargo_toml(package_name: &str) -> String {
+    format!(
+        r#"[package]
+name = "{package_name}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+csv = "1.3"
+serde = {{ version = "1.0", features = ["derive"] }}
+"#
+    )
+}
+
+fn main_rs(inputs: &[Input]) -> String {
+    // A single input can be swapped for another file with the same layout.
+    let counts = inputs.iter().map(|input| {
+        let path = match (&input.path, inputs.len()) {
+            (Some(path), 1) => {
+                let path = absolute(path);
+                quote! {std::env::args().nth(1).unwrap_or_else(|| #path.to_string())}
+            }
+            (Some(path), _) => {
+                let path = absolute(path);
+                quote! {#path.to_string()}
+            }
+            (None, _) => quote! {std::env::args().nth(1).expect("Pass the CSV file to read.")},
+        };
+        let type_name = format_ident!("{}", input.type_name);
+        let builder = readers::reader_builder(&input.config);
+
+        quote! {
+            let path = #path;
+            let reader = #builder.from_path(&path)?;
+            let rows = count::<model::#type_name>(reader)?;
+            println!("{path}: {rows} rows");
+        }
+    });
+
+    let items = [
+        quote! {mod model;},
+        quote! {
+            fn count<T: serde::de::DeserializeOwned>(
+                mut reader: csv::Reader<std::fs::File>,
+            ) -> Result<usize, csv::Error> {
+                let mut rows = 0;
+                for record in reader.deserialize::<T>() {
+                    record?;
+                    rows += 1;
+                }
+
+                Ok(rows)
+            }
+        },
+        quote! {
+            fn main() -> Result<(), Box<dyn std::error::Error>> {
+                #(#counts)*
+                Ok(())
+            }
+        },
+    ];
+
+    items
+        .into_iter()
+        .map(|item| prettyplease::unparse(&syn::parse2(item).expect("The scaffold is valid Rust.")))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn absolute(path: &Path) -> String {
+    path.canonicalize()
+        .unwrap_or_else(|_| path.to_path_buf())
+        .display()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{main_rs, Input};
+    use crate::Config;
+
+    #[test]
+    fn reader_settings() {
+        let config = Config::builder()
+            .delimiter(b';')
+            .quote(b'\'')
+            .escape(Some(b'\\'))
+            .double_quote(false)
+            .build();
+        let main = main_rs(&[Input {
+            type_name: "Order".to_string(),
+            path: None,
+            config,
+        }]);
+
+        assert!(main.contains(".delimiter(b';')"), "{main}");
+        assert!(main.contains(".quote(b'\\'')"));
+        assert!(main.contains(".escape(Some(b'\\\\'))"));
+        assert!(main.contains(".double_quote(false)"));
+        assert!(main.contains("count::<model::Order>(reader)?"));
+    }
+}
@@ -0,0 +1,72 @@
+use std::io;
+
+/// Discards the first `remaining` lines of the inner reader, e.g. a preamble
+/// preceding the header row.
+pub struct SkipRows<R> {
+    inner: R,
+    remaining: usize,
+}
+
+impl<R> SkipRows<R> {
+    pub fn new(inner: R, rows: usize) -> Self {
+        SkipRows {
+            inner,
+            remaining: rows,
+        }
+    }
+}
+
+impl<R: io::Read> io::Read for SkipRows<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.remaining > 0 {
+            let n = self.inner.read(buf)?;
+            if n == 0 {
+                return Ok(0);
+            }
+
+            let mut start = 0;
+            while self.remaining > 0 {
+                match buf[start..n].iter().position(|&b| b == b'\n') {
+                    Some(i) => {
+                        start += i + 1;
+                        self.remaining -= 1;
+                    }
+                    None => {
+                        start = n;
+                        break;
+                    }
+                }
+            }
+
+            if start < n {
+                buf.copy_within(start..n, 0);
+                return Ok(n - start);
+            }
+        }
+
+        self.inner.read(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SkipRows;
+    use std::io::Read;
+
+    #[test]
+    fn skip_rows() {
+        let input = "title\nexported today\nid,name\n1,a\n";
+
+        let mut output = String::new();
+        SkipRows::new(input.as_bytes(), 2)
+            .read_to_string(&mut output)
+            .unwrap();
+        assert_eq!(output, "id,name\n1,a\n");
+
+        let mut output = String::new();
+        SkipRows::new(input.as_bytes(), 10)
+            .read_to_string(&mut output)
+            .unwrap();
+        assert_eq!(output, "");
+    }
+}
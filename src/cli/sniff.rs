@@ -0,0 +1,64 @@
+const CANDIDATES: [u8; 4] = [b',', b';', b'\t', b'|'];
+
+/// Guesses the delimiter from a sample of the input, similar to Python's `csv.Sniffer`.
+///
+/// Prefers the candidate appearing the same, non-zero number of times on every
+/// sampled line, then the one appearing most often overall. Quoted sections are ignored.
+pub fn sniff_delimiter(sample: &[u8]) -> Option<u8> {
+    // The last line is likely cut off by the sample size.
+    let mut lines = sample.split(|&b| b == b'\n').collect::<Vec<_>>();
+    if lines.len() > 1 {
+        lines.pop();
+    }
+
+    let counts = lines
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| count_unquoted(line))
+        .collect::<Vec<_>>();
+
+    let first = counts.first()?;
+
+    let consistent = (0..CANDIDATES.len())
+        .filter(|&i| first[i] > 0 && counts.iter().all(|c| c[i] == first[i]))
+        .max_by_key(|&i| first[i]);
+
+    let frequent = (0..CANDIDATES.len())
+        .map(|i| (i, counts.iter().map(|c| c[i]).sum::<usize>()))
+        .filter(|&(_, total)| total > 0)
+        .max_by_key(|&(_, total)| total)
+        .map(|(i, _)| i);
+
+    consistent.or(frequent).map(|i| CANDIDATES[i])
+}
+
+fn count_unquoted(line: &[u8]) -> [usize; CANDIDATES.len()] {
+    let mut counts = [0; CANDIDATES.len()];
+    let mut quoted = false;
+
+    for &b in line {
+        if b == b'"' {
+            quoted = !quoted;
+        } else if !quoted {
+            if let Some(i) = CANDIDATES.iter().position(|&c| c == b) {
+                counts[i] += 1;
+            }
+        }
+    }
+
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sniff_delimiter;
+
+    #[test]
+    fn sniff() {
+        assert_eq!(sniff_delimiter(b"a,b,c\n1,2,3\n"), Some(b','));
+        assert_eq!(sniff_delimiter(b"a;b;c\n1,5;2,5;3\n"), Some(b';'));
+        assert_eq!(sniff_delimiter(b"a\tb\n\"x,y\"\t2\n"), Some(b'\t'));
+        assert_eq!(sniff_delimiter(b"a|b|c\n1|2|3\n4|5"), Some(b'|'));
+        assert_eq!(sniff_delimiter(b"single\ncolumn\n"), None);
+    }
+}
@@ -0,0 +1,231 @@
+use std::ops::Range;
+
+use syn::{spanned::Spanned, Item};
+
+use crate::Emit;
+
+/// Generated code in a file with hand-written code is kept between these,
+/// followed by the name of the generated type, as line comments.
+const BEGIN: &str = "csv2serde:begin";
+const END: &str = "csv2serde:end";
+
+/// `existing` with `code` in the format `emit` added at the end between
+/// markers. Imports the file already has are left out. The file is only
+/// parsed if it's Rust.
+pub fn append(existing: &str, code: &str, name: &str, emit: Emit) -> Result<String, String> {
+    let comment = emit
+        .line_comment()
+        .ok_or("--append needs a format with comments")?;
+    if emit == Emit::Rust {
+        parse(existing)?;
+    }
+    if marked(existing, name, comment).is_some() {
+        return Err(format!(
+            "{name} was already generated into this file, pass `--replace-struct {name}` to replace it"
+        ));
+    }
+
+    let code = without_imports(code, existing);
+
+    let mut result = existing.trim_end().to_string();
+    if !result.is_empty() {
+        result.push_str("\n\n");
+    }
+    result.push_str(&block(code.trim_start(), name, comment));
+    Ok(result)
+}
+
+/// `existing` with the struct `name` replaced by the one in `code`: between
+/// markers if it was generated there before, or where it's defined otherwise.
+/// Everything generated along with it, like helpers, nested structs and
+/// imports the file doesn't have yet, goes in its place too.
+pub fn replace_struct(existing: &str, code: &str, name: &str) -> Result<String, String> {
+    let file = parse(existing)?;
+    let generated = syn::parse_file(code).map_err(|e| format!("invalid generated code: {e}"))?;
+    if item_lines(&generated, name).is_none() {
+        return Err(format!("no struct {name} was generated"));
+    }
+
+    let item = without_imports(code, existing);
+
+    let lines = existing.lines().collect::<Vec<_>>();
+    let target = marked(existing, name, "//")
+        .or_else(|| item_lines(&file, name))
+        .ok_or_else(|| format!("there is no struct {name} to replace"))?;
+
+    let mut result = lines[..target.start].join("\n");
+    if !result.is_empty() {
+        result.push('\n');
+    }
+    result.push_str(&block(&item, name, "//"));
+    for line in &lines[target.end..] {
+        result.push_str(line);
+        result.push('\n');
+    }
+    Ok(result)
+}
+
+/// `code` without the `use` lines `existing` already has.
+fn without_imports(code: &str, existing: &str) -> String {
+    code.lines()
+        .filter(|line| !line.starts_with("use ") || !existing.lines().any(|l| l == *line))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+fn parse(existing: &str) -> Result<syn::File, String> {
+    syn::parse_file(existing).map_err(|e| {
+        let line = e.span().start().line;
+        format!("could not parse the existing file, at line {line}: {e}")
+    })
+}
+
+fn block(code: &str, name: &str, comment: &str) -> String {
+    format!(
+        "{comment} {BEGIN} {name}\n{}\n{comment} {END} {name}\n",
+        code.trim_end()
+    )
+}
+
+/// Lines between markers for `name`, including them.
+fn marked(source: &str, name: &str, comment: &str) -> Option<Range<usize>> {
+    let (begin, end) = (
+        format!("{comment} {BEGIN} {name}"),
+        format!("{comment} {END} {name}"),
+    );
+    let lines = source.lines().collect::<Vec<_>>();
+
+    let start = lines.iter().position(|line| line.trim() == begin)?;
+    let len = lines[start..].iter().position(|line| line.trim() == end)?;
+    Some(start..start + len + 1)
+}
+
+/// Lines of the struct `name`, including its attributes and doc comments.
+fn item_lines(file: &syn::File, name: &str) -> Option<Range<usize>> {
+    file.items.iter().find_map(|item| match item {
+        Item::Struct(item) if item.ident == name => {
+            let span = item.span();
+            Some(span.start().line - 1..span.end().line)
+        }
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{append, replace_struct};
+    use crate::Emit;
+    use indoc::indoc;
+
+    const EXISTING: &str = indoc! {"
+        use serde::Deserialize;
+
+        /// Hand-written.
+        #[derive(Debug, Deserialize)]
+        pub struct Order {
+            pub id: u8,
+        }
+
+        impl Order {
+            fn total(&self) -> u32 {
+                0
+            }
+        }
+    "};
+
+    const GENERATED: &str = indoc! {"
+        use serde::Deserialize;
+
+        #[derive(Debug, Deserialize)]
+        pub struct Order {
+            pub id: u64,
+            pub total: f32,
+        }
+    "};
+
+    #[test]
+    fn replace() {
+        let replaced = replace_struct(EXISTING, GENERATED, "Order").unwrap();
+        let expected = indoc! {"
+            use serde::Deserialize;
+
+            // csv2serde:begin Order
+            #[derive(Debug, Deserialize)]
+            pub struct Order {
+                pub id: u64,
+                pub total: f32,
+            }
+            // csv2serde:end Order
+
+            impl Order {
+                fn total(&self) -> u32 {
+                    0
+                }
+            }
+        "};
+        assert_eq!(replaced, expected);
+
+        // Once marked, the block is replaced.
+        assert_eq!(
+            replace_struct(&replaced, GENERATED, "Order").unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn append_once() {
+        let existing = "use serde::Deserialize;\n\nfn main() {}\n";
+        let appended = append(existing, GENERATED, "Order", Emit::Rust).unwrap();
+        assert_eq!(
+            appended,
+            indoc! {"
+                use serde::Deserialize;
+
+                fn main() {}
+
+                // csv2serde:begin Order
+                #[derive(Debug, Deserialize)]
+                pub struct Order {
+                    pub id: u64,
+                    pub total: f32,
+                }
+                // csv2serde:end Order
+            "}
+        );
+
+        assert!(append(&appended, GENERATED, "Order", Emit::Rust).is_err());
+
+        // Other formats are not parsed.
+        let existing = "export interface Item {\n  id: number;\n}\n";
+        let generated = "export interface Order {\n  id: number;\n}\n";
+        assert!(append(existing, generated, "Order", Emit::Typescript).is_ok());
+    }
+
+    #[test]
+    fn replace_with_helpers() {
+        let generated = indoc! {"
+            use serde::Deserialize;
+            use std::time::Duration;
+
+            #[derive(Debug, Deserialize)]
+            pub struct Order {
+                #[serde(deserialize_with = \"deserialize_duration\")]
+                pub took: Duration,
+            }
+
+            fn deserialize_duration() {}
+        "};
+
+        let replaced = replace_struct(EXISTING, generated, "Order").unwrap();
+        assert!(replaced.contains(indoc! {"
+            // csv2serde:begin Order
+            use std::time::Duration;
+
+            #[derive(Debug, Deserialize)]
+        "}));
+        assert!(replaced.contains("fn deserialize_duration() {}\n// csv2serde:end Order\n"));
+        assert_eq!(replaced.matches("use serde::Deserialize;").count(), 1);
+    }
+}
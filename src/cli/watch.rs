@@ -0,0 +1,56 @@
+use std::{
+    path::PathBuf,
+    sync::mpsc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use notify::{EventKind, RecursiveMode, Watcher};
+
+/// How long to wait for the writes of a change to settle.
+const SETTLE: Duration = Duration::from_millis(200);
+
+/// Calls `on_change` whenever any of the files is modified, until the watcher
+/// fails. Their directories are watched rather than the files, so that files
+/// replaced by renaming another over them are still noticed.
+pub fn watch(files: &[PathBuf], mut on_change: impl FnMut()) -> notify::Result<()> {
+    let files = files
+        .iter()
+        .map(|path| path.canonicalize())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    for dir in files.iter().filter_map(|path| path.parent()) {
+        watcher.watch(dir, RecursiveMode::NonRecursive)?;
+    }
+
+    let concerns_files = |event: &notify::Event| {
+        matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+            && event
+                .paths
+                .iter()
+                .any(|path| files.iter().any(|f| f == path))
+    };
+
+    while let Ok(event) = receiver.recv() {
+        if !concerns_files(&event?) {
+            continue;
+        }
+
+        // A single save can take several writes.
+        std::thread::sleep(SETTLE);
+        receiver.try_iter().for_each(drop);
+        on_change();
+    }
+
+    Ok(())
+}
+
+/// The time of day, in UTC, as `HH:MM:SS`.
+pub fn timestamp() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let (hours, minutes, seconds) = (seconds / 3600 % 24, seconds / 60 % 60, seconds % 60);
+    format!("{hours:02}:{minutes:02}:{seconds:02}")
+}
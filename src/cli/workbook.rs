@@ -0,0 +1,34 @@
+use std::{io, path::Path};
+
+use calamine::{open_workbook_auto, Reader};
+
+/// Converts a worksheet into CSV, so it can go through the same inference as any other input.
+/// Uses the first sheet if `sheet` is not provided.
+pub fn sheet_to_csv<P: AsRef<Path>>(
+    path: P,
+    sheet: Option<&str>,
+    delimiter: u8,
+) -> io::Result<Vec<u8>> {
+    let mut workbook = open_workbook_auto(path).map_err(io::Error::other)?;
+
+    let sheet = match sheet {
+        Some(sheet) => sheet.to_string(),
+        None => workbook
+            .sheet_names()
+            .first()
+            .cloned()
+            .ok_or_else(|| io::Error::other("Workbook has no sheets."))?,
+    };
+
+    let range = workbook.worksheet_range(&sheet).map_err(io::Error::other)?;
+
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(vec![]);
+
+    for row in range.rows() {
+        writer.write_record(row.iter().map(|cell| cell.to_string()))?;
+    }
+
+    writer.into_inner().map_err(io::Error::other)
+}
@@ -1,6 +1,6 @@
 use std::{fs::File, io};
 
-use crate::CLI;
+use crate::cli::CLI;
 
 pub enum WriteDestination {
     File(File),
@@ -35,6 +35,7 @@ impl TryFrom<&CLI> for WriteDestination {
                 let f = File::options()
                     .read(false)
                     .write(true)
+                    .create(true)
                     .create_new(!cli.force)
                     .truncate(true)
                     .open(path)?;
@@ -1,31 +1,590 @@
+mod helpers;
+mod imports;
+mod nesting;
+pub(crate) mod readers;
+mod rename;
+mod sample_tests;
 mod styling;
+mod validation;
 
-use crate::{field::Field, Config, Error};
-use quote::{format_ident, quote};
+use crate::{field::Field, Config, Emitter, EmptyColumns, Error, Schema, Visibility};
+use convert_case::{Case, Casing};
+use quote::quote;
 
-pub fn generate(config: &Config, fields: Vec<Field>) -> Result<String, Error> {
-    let struct_name = format_ident!("{}", config.struct_name);
+pub use sample_tests::Sample;
 
-    let fields = fields.iter().map(|f| {
-        let field_name = format_ident!("{}", &f.name);
-        let type_name = syn::Type::Verbatim(f.type_name().parse().unwrap());
+/// Renders each table as a struct deriving `Deserialize`, or with
+/// `config.split_by`, as an enum over a struct per group, picked by the
+/// value of the tag column.
+pub struct RustStructs;
 
-        let maybe_rename = if f.name != f.raw_name {
+impl Emitter for RustStructs {
+    fn emit(&self, schema: &Schema, config: &Config) -> Result<String, Error> {
+        let mut enums = vec![];
+        let mut structs = vec![];
+        let mut samples = vec![];
+        let mut dialects = vec![];
+
+        for table in &schema.tables {
+            let name = table.name.clone();
+            let redacted = table
+                .fields()
+                .iter()
+                .enumerate()
+                .filter(|(_, f)| f.is_redacted())
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>();
+            samples.push((name.clone(), table.sample.redacted(&redacted)));
+            dialects.push((name.clone(), table.dialect(config)));
+
+            let Some(split_by) = &config.split_by else {
+                let fields = table
+                    .groups
+                    .first()
+                    .map(|(_, f)| f.clone())
+                    .unwrap_or_default();
+                structs.push((name, fields));
+                continue;
+            };
+
+            let split_by = std::slice::from_ref(split_by);
+            let mut tag = String::new();
+            let mut variants = vec![];
+
+            for (key, fields) in &table.groups {
+                // The tag is consumed by serde, so it can't also be a field.
+                if let Some(f) = fields.iter().find(|f| f.is_listed(split_by)) {
+                    tag = f.raw_name.clone();
+                }
+                let mut fields = fields.clone();
+                fields.retain(|f| !f.is_listed(split_by));
+
+                let variant = variant_name(key);
+                variants.push((key.clone(), variant.clone()));
+                structs.push((variant, fields));
+            }
+
+            enums.push(TaggedEnum {
+                name,
+                tag,
+                variants,
+            });
+        }
+
+        generate(config, enums, structs, samples, &dialects)
+    }
+}
+
+/// An enum tagged by a column, with a newtype variant per struct.
+struct TaggedEnum {
+    name: String,
+    /// Raw name of the column holding the tag.
+    tag: String,
+    /// Tag values, and the names of the structs they map to.
+    variants: Vec<(String, String)>,
+}
+
+/// Converts a tag value, like `refund` or `ADJUSTMENT`, into a variant name.
+fn variant_name(tag: &str) -> String {
+    let name = tag
+        .replace(|c: char| c.is_ascii_punctuation(), "_")
+        .to_case(Case::Pascal);
+
+    match name.chars().next() {
+        Some(c) if c.is_alphabetic() => name,
+        _ => format!("Variant{name}"),
+    }
+}
+
+/// Generates the given enums, then one struct per `(name, fields)` pair,
+/// followed by any helpers they need, and tests over the samples of each
+/// top-level type.
+fn generate(
+    config: &Config,
+    enums: Vec<TaggedEnum>,
+    structs: Vec<(String, Vec<Field>)>,
+    samples: Vec<(String, Sample)>,
+    dialects: &[(String, Config)],
+) -> Result<String, Error> {
+    // Readers and tests of each top-level type use the dialect of its input.
+    let dialect = |name: &str| {
+        dialects
+            .iter()
+            .find(|(n, _)| n == name)
+            .map_or(config, |(_, config)| config)
+    };
+
+    let mut items = vec![];
+    let mut helpers = vec![];
+
+    // Types users deserialize records into: enums, and structs not wrapped in one.
+    let mut roots = enums
+        .iter()
+        .map(|e| {
+            (
+                e.name.clone(),
+                e.variants.iter().map(|(_, v)| v.clone()).collect(),
+            )
+        })
+        .collect::<Vec<(String, Vec<String>)>>();
+    roots.extend(
+        structs
+            .iter()
+            .filter(|(name, _)| {
+                !enums
+                    .iter()
+                    .any(|e| e.variants.iter().any(|(_, v)| v == name))
+            })
+            .map(|(name, _)| (name.clone(), vec![name.clone()])),
+    );
+
+    // How the columns of each top-level type's input line up with its
+    // fields: captured columns beyond the header row are named after the
+    // fields holding them, and repeated headers are read by position.
+    let columns = roots
+        .iter()
+        .map(|(name, members)| {
+            let mut columns = readers::Columns::default();
+            for (_, fields) in structs.iter().filter(|(n, _)| members.contains(n)) {
+                for (i, field) in fields.iter().enumerate() {
+                    if field.is_extra() && !columns.extra.contains(&field.raw_name) {
+                        columns.extra.push(field.raw_name.clone());
+                    }
+                    if fields[..i].iter().any(|f| f.raw_name == field.raw_name) {
+                        columns.by_position = true;
+                    }
+                }
+            }
+            (name.clone(), columns)
+        })
+        .collect::<Vec<_>>();
+    let default_columns = readers::Columns::default();
+    let columns = |name: &str| {
+        columns
+            .iter()
+            .find(|(n, _)| n == name)
+            .map_or(&default_columns, |(_, columns)| columns)
+    };
+
+    let structs = structs
+        .into_iter()
+        .flat_map(|(struct_name, mut fields)| {
+            fields.retain(|f| !f.is_listed(&config.skip_columns));
+
+            if config.empty_columns == EmptyColumns::Skip {
+                fields.retain(|f| !f.is_empty());
+            }
+
+            match &config.nest_separator {
+                Some(separator) => nesting::nest(struct_name, fields, separator, config),
+                None => vec![(struct_name, fields)],
+            }
+        })
+        .map(|(struct_name, mut fields)| {
+            if config.sort_fields && config.has_headers && !config.no_rename {
+                sort_fields(&struct_name, &mut fields);
+            }
+            (struct_name, fields)
+        })
+        .collect::<Vec<_>>();
+
+    // Structs with borrowed fields need a lifetime.
+    let borrowed = structs
+        .iter()
+        .filter(|(_, fields)| fields.iter().any(Field::is_borrowed))
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+
+    for tagged_enum in enums {
+        items.push(generate_enum(config, &tagged_enum)?);
+
+        if config.with_validation {
+            let name = ident(&tagged_enum.name, &tagged_enum.name)?;
+            let variants = tagged_enum
+                .variants
+                .iter()
+                .map(|(value, variant)| ident(variant, value))
+                .collect::<Result<Vec<_>, Error>>()?;
+            let is_borrowed = tagged_enum
+                .variants
+                .iter()
+                .any(|(_, variant)| borrowed.contains(&variant.as_str()));
+
+            let vis = visibility(config);
+            let validate =
+                validation::for_enum(&vis, &name, anonymous_lifetime(is_borrowed), &variants);
+            let syntax_tree = syn::parse2(validate).map_err(Error::CantGenerateCode)?;
+            items.push(prettyplease::unparse(&syntax_tree));
+        }
+    }
+
+    if config.skip_serializing_none && config.with_writer {
+        log::warn!("not skipping serializing `None` fields, as written rows need every column");
+    }
+
+    for (struct_name, fields) in &structs {
+        helpers.extend(fields.iter().filter_map(Field::deserialize_with));
+        if config.with_writer {
+            helpers.extend(fields.iter().filter_map(Field::serialize_with));
+        }
+        items.push(generate_struct(config, struct_name, fields)?);
+
+        if config.with_validation {
+            let name = ident(struct_name, struct_name)?;
+            let is_borrowed = fields.iter().any(Field::is_borrowed);
+
+            let vis = visibility(config);
+            let validate =
+                validation::for_struct(&vis, &name, anonymous_lifetime(is_borrowed), fields);
+            let syntax_tree = syn::parse2(validate).map_err(Error::CantGenerateCode)?;
+            items.push(prettyplease::unparse(&syntax_tree));
+        }
+    }
+
+    if config.with_validation {
+        for item in validation::error_type(&visibility(config)) {
+            let syntax_tree = syn::parse2(item).map_err(Error::CantGenerateCode)?;
+            items.push(prettyplease::unparse(&syntax_tree));
+        }
+    }
+
+    if config.with_reader || config.with_iter || config.with_writer {
+        let vis = visibility(config);
+
+        for (name, members) in &roots {
+            let is_borrowed = members.iter().any(|m| borrowed.contains(&m.as_str()));
+            let mut functions = vec![];
+
+            // A borrowing type can't outlive the reader, so it can't be collected.
+            if is_borrowed && (config.with_reader || config.with_iter) {
+                log::warn!("skipping the reader functions for borrowing type {name}");
+            } else {
+                if config.with_reader {
+                    functions.push(readers::read_all(dialect(name), &vis, name, columns(name)));
+                }
+                if config.with_iter {
+                    functions.push(readers::iterate(dialect(name), &vis, name, columns(name)));
+                }
+            }
+            // csv can't write the maps serde turns flattened structs into.
+            let is_nested = structs
+                .iter()
+                .filter(|(n, _)| members.contains(n))
+                .any(|(_, fields)| fields.iter().any(Field::is_flattened));
+            if is_nested && config.with_writer {
+                log::warn!("skipping the writer function for nested type {name}");
+            } else if config.with_writer {
+                functions.push(readers::write_all(dialect(name), &vis, name, is_borrowed));
+            }
+
+            for function in functions {
+                let syntax_tree = syn::parse2(function).map_err(Error::CantGenerateCode)?;
+                items.push(prettyplease::unparse(&syntax_tree));
+            }
+        }
+    }
+
+    helpers.sort();
+    helpers.dedup();
+
+    for helper in helpers.into_iter().filter_map(helpers::by_name) {
+        let syntax_tree = syn::parse2(helper).map_err(Error::CantGenerateCode)?;
+        items.push(prettyplease::unparse(&syntax_tree));
+    }
+
+    if config.with_imports {
+        let imports = imports::generate(&derives(config)?, structs.iter().flat_map(|(_, f)| f));
+        let syntax_tree = syn::parse2(imports).map_err(Error::CantGenerateCode)?;
+        items.insert(0, prettyplease::unparse(&syntax_tree));
+    }
+
+    if config.with_tests {
+        let samples = samples
+            .into_iter()
+            .map(|(name, sample)| {
+                (
+                    dialect(&name),
+                    columns(&name).extra.as_slice(),
+                    name,
+                    sample,
+                )
+            })
+            .collect::<Vec<_>>();
+        let tests = sample_tests::generate(&samples);
+        let syntax_tree = syn::parse2(tests).map_err(Error::CantGenerateCode)?;
+        items.push(prettyplease::unparse(&syntax_tree));
+    }
+
+    Ok(items.join("\n"))
+}
+
+/// Orders fields by name, unless repeated headers mean some records have to
+/// be read by position.
+fn sort_fields(struct_name: &str, fields: &mut [Field]) {
+    let repeats = fields
+        .iter()
+        .enumerate()
+        .any(|(i, f)| fields[..i].iter().any(|other| other.raw_name == f.raw_name));
+
+    if repeats {
+        log::warn!("keeping the column order of {struct_name}, as its headers repeat");
+        return;
+    }
+
+    fields.sort_by(|a, b| {
+        a.name
+            .trim_start_matches("r#")
+            .cmp(b.name.trim_start_matches("r#"))
+    });
+}
+
+/// `Debug` and `Deserialize`, with `Serialize` for writer functions, followed
+/// by any additional derives from the config.
+fn derives(config: &Config) -> Result<Vec<syn::Path>, Error> {
+    let mut names = vec!["Debug", "Deserialize"];
+    if config.with_writer {
+        names.push("Serialize");
+    }
+    for name in &config.derives {
+        if !names.contains(&name.as_str()) {
+            names.push(name);
+        }
+    }
+
+    names
+        .into_iter()
+        .map(|name| syn::parse_str(name).map_err(Error::CantGenerateCode))
+        .collect()
+}
+
+fn visibility(config: &Config) -> proc_macro2::TokenStream {
+    match config.visibility {
+        Visibility::Pub => quote! {pub},
+        Visibility::PubCrate => quote! {pub(crate)},
+        Visibility::Private => quote! {},
+    }
+}
+
+/// The `<'a>` generics of a type borrowing from the input.
+fn lifetime(borrowed: bool) -> proc_macro2::TokenStream {
+    if borrowed {
+        quote! {<'a>}
+    } else {
+        quote! {}
+    }
+}
+
+/// The `<'_>` generics of a borrowing type in an `impl` block or a signature.
+fn anonymous_lifetime(borrowed: bool) -> proc_macro2::TokenStream {
+    if borrowed {
+        quote! {<'_>}
+    } else {
+        quote! {}
+    }
+}
+
+/// Parses a name generated from `header` as an identifier, which names kept
+/// as they were in the input may not be.
+fn ident(name: &str, header: &str) -> Result<syn::Ident, Error> {
+    syn::parse_str(name).map_err(|_| Error::InvalidIdentifier {
+        header: header.to_string(),
+        name: name.to_string(),
+    })
+}
+
+/// Silences the lint for type names kept as they were in the file name.
+fn allow_non_camel_case(name: &str) -> proc_macro2::TokenStream {
+    if name.contains('_') || name.starts_with(char::is_lowercase) {
+        quote! {#[allow(non_camel_case_types)]}
+    } else {
+        quote! {}
+    }
+}
+
+fn generate_enum(config: &Config, tagged_enum: &TaggedEnum) -> Result<String, Error> {
+    // csv can't buffer records for serde's internally tagged enums, so
+    // `Deserialize` is implemented below rather than derived.
+    let derives = derives(config)?
+        .into_iter()
+        .filter(|path| !path.is_ident("Deserialize"))
+        .collect::<Vec<_>>();
+    let vis = visibility(config);
+    let maybe_allow = allow_non_camel_case(&tagged_enum.name);
+    let enum_name = ident(&tagged_enum.name, &tagged_enum.name)?;
+    let tag = &tagged_enum.tag;
+
+    // Serialization is still derived, writing the tag as a column.
+    let (maybe_tag, maybe_rename) = if config.with_writer {
+        (quote! {#[serde(tag = #tag)]}, true)
+    } else {
+        (quote! {}, false)
+    };
+
+    let mut variants = vec![];
+    let mut arms = vec![];
+    for (value, name) in &tagged_enum.variants {
+        let name = ident(name, value)?;
+        let rename = maybe_rename.then(|| quote! {#[serde(rename = #value)]});
+        variants.push(quote! {
+            #rename
+            #name(#name),
+        });
+        arms.push(quote! {
+            #value => values.deserialize(Some(&headers)).map(#enum_name::#name),
+        });
+    }
+    let values = tagged_enum.variants.iter().map(|(value, _)| value);
+
+    let full = quote! {
+        #[derive(#(#derives),*)]
+        #maybe_tag
+        #maybe_allow
+        #vis enum #enum_name {
+            #(#variants)*
+        }
+    };
+    let deserialize = quote! {
+        impl<'de> Deserialize<'de> for #enum_name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                use serde::de::Error;
+                use std::collections::HashMap;
+
+                let row: HashMap<String, String> = Deserialize::deserialize(deserializer)?;
+                let tag = row.get(#tag).ok_or_else(|| D::Error::missing_field(#tag))?;
+                let headers = row.keys().collect::<csv::StringRecord>();
+                let values = row.values().collect::<csv::StringRecord>();
+
+                let variant = match tag.as_str() {
+                    #(#arms)*
+                    other => return Err(D::Error::unknown_variant(other, &[#(#values),*])),
+                };
+                variant.map_err(D::Error::custom)
+            }
+        }
+    };
+
+    [full, deserialize]
+        .into_iter()
+        .map(|item| {
+            let syntax_tree = syn::parse2(item).map_err(Error::CantGenerateCode)?;
+            Ok(prettyplease::unparse(&syntax_tree))
+        })
+        .collect::<Result<Vec<_>, Error>>()
+        .map(|items| items.join("\n"))
+}
+
+fn generate_struct(config: &Config, struct_name: &str, fields: &[Field]) -> Result<String, Error> {
+    let derives = derives(config)?;
+    let vis = visibility(config);
+    let maybe_allow_camel = allow_non_camel_case(struct_name);
+    let struct_name = ident(struct_name, struct_name)?;
+    let generics = lifetime(fields.iter().any(Field::is_borrowed));
+
+    // Fields named after headers may not follow Rust's conventions.
+    let maybe_allow = if fields.iter().any(|f| f.name.contains(char::is_uppercase)) {
+        quote! {#[allow(non_snake_case)]}
+    } else {
+        quote! {}
+    };
+
+    let rename_all = if config.no_rename {
+        None
+    } else {
+        rename::detect(fields)
+    };
+
+    // A skipped `None` would leave its row short of a cell.
+    let skip_serializing_none = config.skip_serializing_none && !config.with_writer;
+    let maybe_rename_all = match rename_all {
+        Some(rule) => quote! {#[serde(rename_all = #rule)]},
+        None => quote! {},
+    };
+
+    let fields = fields.iter().enumerate().map(|(i, f)| {
+        let field_name = ident(&f.name, &f.raw_name)?;
+        let type_name =
+            syn::parse_str::<syn::Type>(&f.type_name()).map_err(|_| Error::InvalidType {
+                header: f.raw_name.clone(),
+                type_name: f.type_name(),
+            })?;
+
+        let maybe_rename = if f.is_flattened() {
+            quote! {#[serde(flatten)]}
+        } else if rename::apply(rename_all, &f.name) == f.raw_name {
+            quote! {}
+        } else if config.no_rename || fields[..i].iter().any(|o| o.raw_name == f.raw_name) {
+            // A second rename to the same header could never match, as
+            // records with repeated headers are read by position.
+            let column = format!(" Column `{}`.", f.raw_name);
+            quote! {#[doc = #column]}
+        } else {
             let raw_name = &f.raw_name;
             quote! {#[serde(rename = #raw_name)]}
+        };
+
+        let maybe_deserialize_with = match f.deserialize_with() {
+            Some(helper) => quote! {#[serde(deserialize_with = #helper)]},
+            None => quote! {},
+        };
+
+        let maybe_serialize_with = match f.serialize_with().filter(|_| config.with_writer) {
+            Some(helper) => quote! {#[serde(serialize_with = #helper)]},
+            None => quote! {},
+        };
+
+        let maybe_default = if config.serde_default && f.is_option() {
+            quote! {#[serde(default)]}
+        } else {
+            quote! {}
+        };
+
+        let maybe_skip_serializing = if skip_serializing_none && f.is_option() {
+            quote! {#[serde(skip_serializing_if = "Option::is_none")]}
+        } else {
+            quote! {}
+        };
+
+        let maybe_borrow = if f.is_borrowed() {
+            quote! {#[serde(borrow)]}
         } else {
             quote! {}
         };
 
-        quote! {
+        // Samples of redacted values would give them away.
+        let docs = f
+            .stats
+            .iter()
+            .filter(|_| config.stats_docs && !f.is_redacted())
+            .flat_map(|stats| {
+                let numeric = f.type_parser().is_some_and(|p| p.is_numeric());
+                stats.describe(numeric).into_iter().map(|line| {
+                    let line = format!(" {line}");
+                    quote! {#[doc = #line]}
+                })
+            });
+
+        Ok(quote! {
+            #(#docs)*
             #maybe_rename
-            pub #field_name: #type_name,
-        }
+            #maybe_deserialize_with
+            #maybe_serialize_with
+            #maybe_default
+            #maybe_skip_serializing
+            #maybe_borrow
+            #vis #field_name: #type_name,
+        })
     });
+    let fields = fields.collect::<Result<Vec<_>, Error>>()?;
 
     let full = quote! {
-        #[derive(Debug, Deserialize)]
-        pub struct #struct_name {
+        #[derive(#(#derives),*)]
+        #maybe_rename_all
+        #maybe_allow
+        #maybe_allow_camel
+        #vis struct #struct_name #generics {
             #(#fields)*
         }
     };
@@ -35,3 +594,140 @@ pub fn generate(config: &Config, fields: Vec<Field>) -> Result<String, Error> {
 
     Ok(styling::add_blank_lines(result, config.blank_lines))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use indoc::indoc;
+
+    use crate::{run_on_str, Config, Error};
+
+    #[test]
+    fn no_rename() {
+        let config = Config::builder()
+            .no_rename(true)
+            .with_imports(false)
+            .build();
+
+        assert_eq!(
+            run_on_str("Order ID,total\n1,2\n", &config).unwrap(),
+            indoc! {"
+                #[derive(Debug, Deserialize)]
+                pub struct Record {
+                    /// Column `Order ID`.
+                    pub order_id: u8,
+
+                    pub total: u8,
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn sort_fields() {
+        let config = Config::builder()
+            .sort_fields(true)
+            .with_imports(false)
+            .blank_lines(0usize)
+            .build();
+
+        assert_eq!(
+            run_on_str("type,Amount,id\nxy,1,2\n", &config).unwrap(),
+            indoc! {"
+                #[derive(Debug, Deserialize)]
+                pub struct Record {
+                    #[serde(rename = \"Amount\")]
+                    pub amount: u8,
+                    pub id: u8,
+                    pub r#type: String,
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn with_writer() {
+        let config = Config::builder().with_writer(true).build();
+        let code = run_on_str("a\n1\n", &config).unwrap();
+
+        assert!(code.contains("#[derive(Debug, Deserialize, Serialize)]"));
+        assert!(code.contains("use serde::Serialize;"));
+        assert!(code.contains(
+            "pub fn write_records(\n    path: impl AsRef<std::path::Path>,\n    rows: &[Record],"
+        ));
+
+        // csv can't write the flattened structs of nested types.
+        let config = Config::builder()
+            .with_writer(true)
+            .nest_separator("_".to_string())
+            .build();
+        let code = run_on_str("a_x,a_y\n1,2\n", &config).unwrap();
+        assert!(code.contains("#[serde(flatten)]"), "{code}");
+        assert!(!code.contains("fn write_records"), "{code}");
+    }
+
+    #[test]
+    fn skip_serializing_none() {
+        let config = Config::builder()
+            .skip_serializing_none(true)
+            .with_imports(false)
+            .blank_lines(0usize)
+            .build();
+
+        assert_eq!(
+            run_on_str("id,note\n1,\n", &config).unwrap(),
+            indoc! {r#"
+                #[derive(Debug, Deserialize)]
+                pub struct Record {
+                    pub id: u8,
+                    #[serde(skip_serializing_if = "Option::is_none")]
+                    pub note: Option<()>,
+                }
+            "#}
+        );
+    }
+
+    #[test]
+    fn redact() {
+        let config = Config::builder()
+            .redact_columns(vec!["ssn".to_string()])
+            .stats_docs(true)
+            .with_tests(true)
+            .build();
+        let code = run_on_str("id,ssn\n1,123-45-6789\n", &config).unwrap();
+
+        assert!(
+            code.contains("#[serde(deserialize_with = \"redact_string\")]\n    pub ssn: String,")
+        );
+        assert!(code.contains("fn redact_string<'de, D>"));
+        assert!(!code.contains("123-45-6789"), "{code}");
+    }
+
+    #[test]
+    fn sample_with_carriage_return() {
+        let config = Config::builder().with_tests(true).build();
+        let code = run_on_str("id,note\n1,\"a\rb\"\n", &config).unwrap();
+
+        assert!(
+            code.contains(r#"const RECORD_SAMPLE: &str = "id,note\n1,\"a\rb\"\n";"#),
+            "{code}"
+        );
+    }
+
+    #[test]
+    fn invalid_names() {
+        let config = Config::builder().struct_name("My Record").build();
+        assert!(matches!(
+            run_on_str("a\n1\n", &config),
+            Err(Error::InvalidIdentifier { name, .. }) if name == "My Record"
+        ));
+
+        let overrides = HashMap::from([("a".to_string(), "Vec<".to_string())]);
+        let config = Config::builder().type_overrides(overrides).build();
+        assert!(matches!(
+            run_on_str("a\n1\n", &config),
+            Err(Error::InvalidType { header, .. }) if header == "a"
+        ));
+    }
+}
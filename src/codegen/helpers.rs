@@ -0,0 +1,322 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Returns the helper function with the given name, as referenced by
+/// `#[serde(deserialize_with)]` and `#[serde(serialize_with)]` attributes.
+pub fn by_name(name: &str) -> Option<TokenStream> {
+    match name {
+        "deserialize_map" => Some(quote! {
+            fn deserialize_map<'de, D>(
+                deserializer: D,
+            ) -> Result<std::collections::HashMap<String, String>, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                // Rows of a flexible file may end before the cell.
+                let cell = <Option<String> as serde::Deserialize>::deserialize(deserializer)?;
+                let cell = cell.unwrap_or_default();
+                let cell = cell.strip_suffix(';').unwrap_or(&cell);
+
+                if cell.is_empty() {
+                    return Ok(Default::default());
+                }
+
+                cell.split(';')
+                    .map(|pair| {
+                        pair.split_once('=')
+                            .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+                            .ok_or_else(|| {
+                                serde::de::Error::custom(format!("invalid key=value pair: {pair}"))
+                            })
+                    })
+                    .collect()
+            }
+        }),
+
+        "deserialize_duration" => {
+            let parse = parse_duration();
+            Some(quote! {
+                fn deserialize_duration<'de, D>(
+                    deserializer: D,
+                ) -> Result<std::time::Duration, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    #parse
+
+                    let cell = <String as serde::Deserialize>::deserialize(deserializer)?;
+                    parse(&cell).ok_or_else(|| {
+                        serde::de::Error::custom(format!("invalid duration: {cell}"))
+                    })
+                }
+            })
+        }
+
+        "deserialize_optional_duration" => {
+            let parse = parse_duration();
+            Some(quote! {
+                fn deserialize_optional_duration<'de, D>(
+                    deserializer: D,
+                ) -> Result<Option<std::time::Duration>, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    #parse
+
+                    // Empty, or missing from a row of a flexible file.
+                    let Some(cell) = <Option<String> as serde::Deserialize>::deserialize(deserializer)?
+                    else {
+                        return Ok(None);
+                    };
+
+                    parse(&cell).map(Some).ok_or_else(|| {
+                        serde::de::Error::custom(format!("invalid duration: {cell}"))
+                    })
+                }
+            })
+        }
+
+        "deserialize_flattened_option" => Some(quote! {
+            fn deserialize_flattened_option<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+                T: std::str::FromStr,
+                T::Err: std::fmt::Display,
+            {
+                // Cells of flattened structs arrive as the numbers or booleans
+                // they look like, so take them back to text before parsing.
+                struct Cell;
+
+                impl serde::de::Visitor<'_> for Cell {
+                    type Value = String;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        f.write_str("a cell")
+                    }
+
+                    fn visit_str<E>(self, v: &str) -> Result<String, E> {
+                        Ok(v.to_string())
+                    }
+
+                    fn visit_bool<E>(self, v: bool) -> Result<String, E> {
+                        Ok(v.to_string())
+                    }
+
+                    fn visit_i64<E>(self, v: i64) -> Result<String, E> {
+                        Ok(v.to_string())
+                    }
+
+                    fn visit_u64<E>(self, v: u64) -> Result<String, E> {
+                        Ok(v.to_string())
+                    }
+
+                    fn visit_f64<E>(self, v: f64) -> Result<String, E> {
+                        Ok(v.to_string())
+                    }
+
+                    fn visit_unit<E>(self) -> Result<String, E> {
+                        Ok(String::new())
+                    }
+                }
+
+                let cell = deserializer.deserialize_any(Cell)?;
+                if cell.is_empty() {
+                    return Ok(None);
+                }
+
+                cell.parse().map(Some).map_err(serde::de::Error::custom)
+            }
+        }),
+
+        "serialize_map" => Some(quote! {
+            fn serialize_map<S>(
+                map: &std::collections::HashMap<String, String>,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                // Sorted, so that writing the same records gives the same file.
+                let mut pairs = map.iter().map(|(k, v)| format!("{k}={v}")).collect::<Vec<_>>();
+                pairs.sort();
+                serializer.serialize_str(&pairs.join(";"))
+            }
+        }),
+
+        "serialize_duration" => {
+            let format = format_duration();
+            Some(quote! {
+                fn serialize_duration<S>(
+                    duration: &std::time::Duration,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error>
+                where
+                    S: serde::Serializer,
+                {
+                    #format
+
+                    serializer.serialize_str(&format(duration))
+                }
+            })
+        }
+
+        "serialize_optional_duration" => {
+            let format = format_duration();
+            Some(quote! {
+                fn serialize_optional_duration<S>(
+                    duration: &Option<std::time::Duration>,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error>
+                where
+                    S: serde::Serializer,
+                {
+                    #format
+
+                    match duration {
+                        Some(duration) => serializer.serialize_str(&format(duration)),
+                        None => serializer.serialize_none(),
+                    }
+                }
+            })
+        }
+
+        "redact_string" => {
+            let mask = mask();
+            Some(quote! {
+                fn redact_string<'de, D>(deserializer: D) -> Result<String, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    #mask
+
+                    let cell = <String as serde::Deserialize>::deserialize(deserializer)?;
+                    Ok(mask(&cell))
+                }
+            })
+        }
+
+        "redact_optional_string" => {
+            let mask = mask();
+            Some(quote! {
+                fn redact_optional_string<'de, D>(
+                    deserializer: D,
+                ) -> Result<Option<String>, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    #mask
+
+                    // Empty, or missing from a row of a flexible file.
+                    let Some(cell) = <Option<String> as serde::Deserialize>::deserialize(deserializer)?
+                    else {
+                        return Ok(None);
+                    };
+
+                    Ok(Some(mask(&cell)))
+                }
+            })
+        }
+
+        _ => None,
+    }
+}
+
+/// Replaces every character of a redacted value, so that only its length is
+/// ever held in memory.
+fn mask() -> TokenStream {
+    quote! {
+        fn mask(cell: &str) -> String {
+            "*".repeat(cell.chars().count())
+        }
+    }
+}
+
+/// Writes durations in the units `parse_duration` reads, like `1h30m`, down
+/// to milliseconds.
+fn format_duration() -> TokenStream {
+    quote! {
+        fn format(duration: &std::time::Duration) -> String {
+            let mut secs = duration.as_secs();
+            let mut cell = String::new();
+
+            for (unit, size) in [("d", 86400), ("h", 3600), ("m", 60), ("s", 1)] {
+                if secs >= size {
+                    cell.push_str(&format!("{}{unit}", secs / size));
+                    secs %= size;
+                }
+            }
+            if duration.subsec_millis() > 0 {
+                cell.push_str(&format!("{}ms", duration.subsec_millis()));
+            }
+
+            if cell.is_empty() {
+                cell.push_str("0s");
+            }
+            cell
+        }
+    }
+}
+
+/// Mirrors `type_parser::parse_duration`, for embedding in generated helpers.
+fn parse_duration() -> TokenStream {
+    quote! {
+        fn parse(cell: &str) -> Option<std::time::Duration> {
+            use std::time::Duration;
+
+            if cell.contains(':') {
+                let parts = cell.split(':').collect::<Vec<_>>();
+                if !(2..=3).contains(&parts.len()) {
+                    return None;
+                }
+
+                let mut secs: u64 = 0;
+                for (i, part) in parts.iter().enumerate() {
+                    if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                        return None;
+                    }
+                    let value = part.parse::<u64>().ok()?;
+                    // Only the leading part may run past a minute or an hour.
+                    if i > 0 && value > 59 {
+                        return None;
+                    }
+                    secs = secs.checked_mul(60)?.checked_add(value)?;
+                }
+
+                return Some(Duration::from_secs(secs));
+            }
+
+            if cell.is_empty() {
+                return None;
+            }
+
+            let mut total = Duration::ZERO;
+            let mut rest = cell;
+
+            while !rest.is_empty() {
+                let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+                if digits == 0 {
+                    return None;
+                }
+
+                let (value, tail) = rest.split_at(digits);
+                let value = value.parse::<u64>().ok()?;
+
+                let unit_len = tail.find(|c: char| c.is_ascii_digit()).unwrap_or(tail.len());
+                let (unit, tail) = tail.split_at(unit_len);
+
+                let part = match unit {
+                    "d" => Duration::from_secs(value.checked_mul(86400)?),
+                    "h" => Duration::from_secs(value.checked_mul(3600)?),
+                    "m" => Duration::from_secs(value.checked_mul(60)?),
+                    "s" => Duration::from_secs(value),
+                    "ms" => Duration::from_millis(value),
+                    _ => return None,
+                };
+                total = total.checked_add(part)?;
+                rest = tail;
+            }
+
+            Some(total)
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::field::Field;
+
+/// Types the generated code may refer to by name, and where they come from.
+const KNOWN: [(&str, &str); 11] = [
+    ("Deserialize", "serde::Deserialize"),
+    ("Serialize", "serde::Serialize"),
+    ("HashMap", "std::collections::HashMap"),
+    ("Duration", "std::time::Duration"),
+    ("PathBuf", "std::path::PathBuf"),
+    ("NaiveDate", "chrono::NaiveDate"),
+    ("NaiveDateTime", "chrono::NaiveDateTime"),
+    ("NaiveTime", "chrono::NaiveTime"),
+    ("DateTime", "chrono::DateTime"),
+    ("Uuid", "uuid::Uuid"),
+    ("Decimal", "rust_decimal::Decimal"),
+];
+
+/// `use` statements for the known types named by the derives or field types.
+/// Paths spelled out in full, like `chrono::NaiveDate`, need no import.
+pub fn generate<'a>(derives: &[syn::Path], fields: impl Iterator<Item = &'a Field>) -> TokenStream {
+    let mut names = derives
+        .iter()
+        .filter_map(|path| path.get_ident().map(|i| i.to_string()))
+        .collect::<Vec<_>>();
+
+    for field in fields {
+        let type_name = field.type_name();
+        let mut rest = type_name.as_str();
+
+        // Only the leading segment of a path can refer to an import.
+        while !rest.is_empty() {
+            let start = rest.trim_start_matches(|c: char| !c.is_alphanumeric() && c != '_');
+            let end = start
+                .find(|c: char| !c.is_alphanumeric() && c != '_')
+                .unwrap_or(start.len());
+            let (name, after) = start.split_at(end);
+
+            let preceded_by_path = rest[..rest.len() - start.len()].ends_with("::");
+            if !preceded_by_path && !after.starts_with("::") {
+                names.push(name.to_string());
+            }
+
+            rest = after;
+        }
+    }
+
+    let mut paths = KNOWN
+        .iter()
+        .filter(|(name, _)| names.iter().any(|n| n == name))
+        .map(|(_, path)| *path)
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let paths = paths
+        .into_iter()
+        .map(|path| syn::parse_str::<syn::Path>(path).unwrap());
+
+    quote! {
+        #(use #paths;)*
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use convert_case::{Case, Casing};
+
+use crate::{field::Field, Config};
+
+/// Moves fields sharing a prefix before `separator` into nested structs, named
+/// after the parent and the prefix. Returns the parent struct, followed by the nested ones.
+pub fn nest(
+    struct_name: String,
+    fields: Vec<Field>,
+    separator: &str,
+    config: &Config,
+) -> Vec<(String, Vec<Field>)> {
+    let prefix_of = |f: &Field| {
+        f.raw_name
+            .split_once(separator)
+            .filter(|(prefix, rest)| !prefix.is_empty() && !rest.is_empty())
+            .map(|(prefix, rest)| (prefix.to_string(), rest.to_string()))
+    };
+
+    // Column count, and whether any column borrows, for each prefix.
+    let mut groups: HashMap<String, (usize, bool)> = HashMap::new();
+    for field in &fields {
+        if let Some((prefix, _)) = prefix_of(field) {
+            let (count, borrowed) = groups.entry(prefix).or_default();
+            *count += 1;
+            *borrowed |= field.is_borrowed();
+        }
+    }
+
+    let mut parent = vec![];
+    let mut nested: Vec<(String, String, Vec<Field>)> = vec![];
+
+    for field in fields {
+        // A prefix used by a single column is just part of its name.
+        let Some((prefix, rest)) = prefix_of(&field).filter(|(p, _)| groups[p].0 > 1) else {
+            parent.push(field);
+            continue;
+        };
+
+        if let Some((_, _, members)) = nested.iter_mut().find(|(p, _, _)| *p == prefix) {
+            members.push(field.renamed(&rest).nested());
+            continue;
+        }
+
+        // The flattened field takes the place of the group's first column.
+        // Named after its parent too, as other inputs may share the prefix.
+        let name = format!(
+            "{struct_name}{}",
+            prefix
+                .replace(|c: char| c.is_ascii_punctuation(), "_")
+                .to_case(Case::Pascal)
+        );
+        let borrowed = groups[&prefix].1;
+        let type_name = if borrowed {
+            format!("{name}<'a>")
+        } else {
+            name.clone()
+        };
+
+        parent.push(Field::flattened(&prefix, &type_name, borrowed, config));
+        nested.push((prefix, name, vec![field.renamed(&rest).nested()]));
+    }
+
+    let mut structs = vec![(struct_name, parent)];
+    structs.extend(nested.into_iter().map(|(_, name, members)| (name, members)));
+    structs
+}
@@ -0,0 +1,221 @@
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::Config;
+
+/// `read_orders` for `read` and `Order`.
+fn function_name(verb: &str, type_name: &str) -> String {
+    let name = type_name.to_case(Case::Snake);
+
+    if name.ends_with('s') {
+        format!("{verb}_{name}")
+    } else {
+        format!("{verb}_{name}s")
+    }
+}
+
+/// A byte literal, spelled out, as `LitByte` would print a plain `59u8`.
+fn byte(b: u8) -> TokenStream {
+    format!("b'{}'", b.escape_ascii()).parse().unwrap()
+}
+
+/// A `csv::ReaderBuilder` with the settings used for inference, leaving out
+/// the ones matching the csv crate's defaults.
+pub fn reader_builder(config: &Config) -> TokenStream {
+    let delimiter = byte(config.delimiter);
+    let mut settings = vec![quote! {.delimiter(#delimiter)}];
+
+    if !config.has_headers {
+        settings.push(quote! {.has_headers(false)});
+    }
+    if config.quote != b'"' {
+        let quote = byte(config.quote);
+        settings.push(quote! {.quote(#quote)});
+    }
+    if let Some(escape) = config.escape.map(byte) {
+        settings.push(quote! {.escape(Some(#escape))});
+    }
+    if !config.double_quote {
+        settings.push(quote! {.double_quote(false)});
+    }
+    if let Some(comment) = config.comment.map(byte) {
+        settings.push(quote! {.comment(Some(#comment))});
+    }
+    if config.flexible {
+        settings.push(quote! {.flexible(true)});
+    }
+
+    let trim = format_ident!("{}", format!("{:?}", config.trim));
+
+    quote! {
+        csv::ReaderBuilder::new()
+            #(#settings)*
+            .trim(csv::Trim::#trim)
+    }
+}
+
+/// A `csv::WriterBuilder` matching [`reader_builder`], so written files read
+/// back the same.
+fn writer_builder(config: &Config) -> TokenStream {
+    let delimiter = byte(config.delimiter);
+    let mut settings = vec![quote! {.delimiter(#delimiter)}];
+
+    if !config.has_headers {
+        settings.push(quote! {.has_headers(false)});
+    }
+    if config.quote != b'"' {
+        let quote = byte(config.quote);
+        settings.push(quote! {.quote(#quote)});
+    }
+    if let Some(escape) = config.escape.map(byte) {
+        settings.push(quote! {.double_quote(false).escape(#escape)});
+    }
+
+    quote! {
+        csv::WriterBuilder::new()
+            #(#settings)*
+    }
+}
+
+/// How the columns of a file line up with the fields of a type.
+#[derive(Clone, Debug, Default)]
+pub struct Columns {
+    /// Captured columns beyond the header row, named after their fields.
+    pub extra: Vec<String>,
+    /// Whether headers repeat, so that records must be read by position.
+    pub by_position: bool,
+}
+
+/// Statements replacing the header row of `reader` with `config.headers`,
+/// and naming the columns beyond it after the fields capturing them, which
+/// are otherwise never filled.
+pub fn set_headers(config: &Config, columns: &Columns) -> Option<TokenStream> {
+    if !config.has_headers || columns.by_position {
+        return None;
+    }
+
+    let extra = &columns.extra;
+    if !config.headers.is_empty() {
+        let names = config.headers.iter().chain(extra);
+        return Some(quote! {
+            reader.headers()?;
+            reader.set_headers(csv::StringRecord::from(vec![#(#names),*]));
+        });
+    }
+    if extra.is_empty() {
+        return None;
+    }
+
+    Some(quote! {
+        let mut headers = reader.headers()?.clone();
+        headers.extend([#(#extra),*]);
+        reader.set_headers(headers);
+    })
+}
+
+/// A function reading all records of a file into the given type.
+pub fn read_all(
+    config: &Config,
+    vis: &TokenStream,
+    type_name: &str,
+    columns: &Columns,
+) -> TokenStream {
+    let function_name = format_ident!("{}", function_name("read", type_name));
+    let type_name = format_ident!("{}", type_name);
+    let builder = reader_builder(config);
+
+    let body = match set_headers(config, columns) {
+        _ if columns.by_position => quote! {
+            #builder
+                .from_path(path)?
+                .records()
+                .map(|record| record?.deserialize(None))
+                .collect()
+        },
+        Some(set_headers) => quote! {
+            let mut reader = #builder.from_path(path)?;
+            #set_headers
+            reader.deserialize().collect()
+        },
+        None => quote! {
+            #builder
+                .from_path(path)?
+                .deserialize()
+                .collect()
+        },
+    };
+
+    quote! {
+        #vis fn #function_name(
+            path: impl AsRef<std::path::Path>,
+        ) -> Result<Vec<#type_name>, csv::Error> {
+            #body
+        }
+    }
+}
+
+/// A function serializing records of the given type to a file, headers first.
+pub fn write_all(
+    config: &Config,
+    vis: &TokenStream,
+    type_name: &str,
+    borrowed: bool,
+) -> TokenStream {
+    let function_name = format_ident!("{}", function_name("write", type_name));
+    let type_name = format_ident!("{}", type_name);
+    let generics = super::anonymous_lifetime(borrowed);
+    let builder = writer_builder(config);
+
+    quote! {
+        #vis fn #function_name(
+            path: impl AsRef<std::path::Path>,
+            rows: &[#type_name #generics],
+        ) -> Result<(), csv::Error> {
+            let mut writer = #builder.from_path(path)?;
+            for row in rows {
+                writer.serialize(row)?;
+            }
+            writer.flush()?;
+            Ok(())
+        }
+    }
+}
+
+/// A function lazily deserializing the records of a file, one at a time.
+pub fn iterate(
+    config: &Config,
+    vis: &TokenStream,
+    type_name: &str,
+    columns: &Columns,
+) -> TokenStream {
+    let function_name = format_ident!("{}", function_name("iter", type_name));
+    let type_name = format_ident!("{}", type_name);
+    let builder = reader_builder(config);
+
+    let body = match set_headers(config, columns) {
+        _ if columns.by_position => quote! {
+            let reader = #builder.from_path(path)?;
+            Ok(reader
+                .into_records()
+                .map(|record| record?.deserialize(None)))
+        },
+        Some(set_headers) => quote! {
+            let mut reader = #builder.from_path(path)?;
+            #set_headers
+            Ok(reader.into_deserialize())
+        },
+        None => quote! {
+            let reader = #builder.from_path(path)?;
+            Ok(reader.into_deserialize())
+        },
+    };
+
+    quote! {
+        #vis fn #function_name(
+            path: impl AsRef<std::path::Path>,
+        ) -> Result<impl Iterator<Item = Result<#type_name, csv::Error>>, csv::Error> {
+            #body
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use crate::field::Field;
+
+/// The `#[serde(rename_all)]` rules, applied to snake_case field names.
+const RULES: [&str; 7] = [
+    "lowercase",
+    "UPPERCASE",
+    "PascalCase",
+    "camelCase",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+    "SCREAMING-KEBAB-CASE",
+];
+
+/// Mirrors what serde does to a field name for the given `rename_all` rule.
+pub fn apply(rule: Option<&str>, name: &str) -> String {
+    let name = name.trim_start_matches("r#");
+
+    match rule {
+        None | Some("lowercase") | Some("snake_case") => name.to_string(),
+        Some("UPPERCASE") | Some("SCREAMING_SNAKE_CASE") => name.to_ascii_uppercase(),
+        Some("kebab-case") => name.replace('_', "-"),
+        Some("SCREAMING-KEBAB-CASE") => name.replace('_', "-").to_ascii_uppercase(),
+        Some("PascalCase") => pascal_case(name),
+        Some("camelCase") => {
+            let pascal = pascal_case(name);
+            let mut chars = pascal.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                None => pascal,
+            }
+        }
+        Some(rule) => unreachable!("Unknown rename rule '{rule}'"),
+    }
+}
+
+fn pascal_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut capitalize = true;
+
+    for c in name.chars() {
+        if c == '_' {
+            capitalize = true;
+        } else if capitalize {
+            result.push(c.to_ascii_uppercase());
+            capitalize = false;
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// Picks the `rename_all` rule leaving the fewest fields needing their own
+/// `#[serde(rename)]`, if any rule does better than none at all.
+pub fn detect(fields: &[Field]) -> Option<&'static str> {
+    let renames_needed = |rule: Option<&str>| {
+        fields
+            .iter()
+            .filter(|f| !f.is_flattened() && apply(rule, &f.name) != f.raw_name)
+            .count()
+    };
+
+    let baseline = renames_needed(None);
+
+    RULES
+        .into_iter()
+        .map(|rule| (rule, renames_needed(Some(rule))))
+        .filter(|&(_, count)| count < baseline)
+        .min_by_key(|&(_, count)| count)
+        .map(|(rule, _)| rule)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::apply;
+
+    #[test]
+    fn rules() {
+        let name = "order_line_id";
+
+        assert_eq!(apply(None, name), "order_line_id");
+        assert_eq!(apply(Some("UPPERCASE"), name), "ORDER_LINE_ID");
+        assert_eq!(apply(Some("PascalCase"), name), "OrderLineId");
+        assert_eq!(apply(Some("camelCase"), name), "orderLineId");
+        assert_eq!(apply(Some("kebab-case"), name), "order-line-id");
+        assert_eq!(apply(Some("SCREAMING-KEBAB-CASE"), name), "ORDER-LINE-ID");
+        assert_eq!(apply(Some("camelCase"), "r#type"), "type");
+    }
+}
@@ -0,0 +1,124 @@
+use convert_case::{Case, Casing};
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+use serde::{Deserialize, Serialize};
+
+use super::readers;
+use crate::Config;
+
+/// The first few records an input was inferred from.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Sample {
+    pub headers: Option<Vec<String>>,
+    pub records: Vec<Vec<String>>,
+}
+
+impl Sample {
+    /// The sample with the given columns blanked out, to leave values that
+    /// must not be seen out of generated code.
+    pub fn redacted(&self, columns: &[usize]) -> Sample {
+        let mut sample = self.clone();
+        for record in &mut sample.records {
+            for &i in columns {
+                if let Some(cell) = record.get_mut(i) {
+                    cell.clear();
+                }
+            }
+        }
+        sample
+    }
+
+    /// Writes the sample back out as CSV, in the input's dialect.
+    fn to_csv(&self, config: &Config) -> String {
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(config.delimiter)
+            .quote(config.quote)
+            .double_quote(config.double_quote)
+            .escape(config.escape.unwrap_or(b'\\'))
+            .flexible(true)
+            .from_writer(vec![]);
+
+        for record in self.headers.iter().chain(&self.records) {
+            writer
+                .write_record(record)
+                .expect("Writing to a Vec can't fail.");
+        }
+
+        let csv = writer.into_inner().expect("Writing to a Vec can't fail.");
+        String::from_utf8(csv).expect("Records are valid UTF-8.")
+    }
+}
+
+fn has_repeats(headers: &[String]) -> bool {
+    headers
+        .iter()
+        .enumerate()
+        .any(|(i, h)| headers.iter().take(i).any(|other| other == h))
+}
+
+/// A test module deserializing each sample into its type, read in the
+/// dialect of its input.
+pub fn generate(samples: &[(&Config, &[String], String, Sample)]) -> TokenStream {
+    let tests = samples
+        .iter()
+        .map(|(config, extra_headers, type_name, sample)| {
+            let snake_name = type_name.to_case(Case::Snake);
+            let const_name = format_ident!("{}_SAMPLE", snake_name.to_ascii_uppercase());
+            let test_name = format_ident!("deserialize_{snake_name}_sample");
+            let type_name = format_ident!("{}", type_name);
+
+            let csv = Literal::string(&sample.to_csv(config));
+            let builder = readers::reader_builder(config);
+
+            // Headers replaced by `config.headers` are replaced again here, as
+            // in readers, followed by captured columns beyond the header row.
+            let names = match &sample.headers {
+                Some(_) if !config.headers.is_empty() => Some(&config.headers),
+                headers => headers.as_ref(),
+            };
+
+            // Repeated headers can't be told apart, and without renames fields
+            // may not match them, so those records are read by position.
+            let (read_headers, headers) = match names {
+                Some(names) if !has_repeats(names) && !config.no_rename => {
+                    let read_headers = if !config.headers.is_empty() {
+                        let names = names.iter().chain(*extra_headers);
+                        quote! {let headers = csv::StringRecord::from(vec![#(#names),*]);}
+                    } else if extra_headers.is_empty() {
+                        quote! {let headers = reader.headers().unwrap().clone();}
+                    } else {
+                        quote! {
+                            let mut headers = reader.headers().unwrap().clone();
+                            headers.extend([#(#extra_headers),*]);
+                        }
+                    };
+                    (read_headers, quote! {Some(&headers)})
+                }
+                _ => (quote! {}, quote! {None}),
+            };
+
+            quote! {
+                const #const_name: &str = #csv;
+
+                #[test]
+                fn #test_name() {
+                    let mut reader = #builder.from_reader(#const_name.as_bytes());
+                    #read_headers
+
+                    for record in reader.records() {
+                        let record = record.unwrap();
+                        record.deserialize::<#type_name>(#headers).unwrap();
+                    }
+                }
+            }
+        });
+
+    quote! {
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+
+            #(#tests)*
+        }
+    }
+}
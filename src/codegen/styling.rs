@@ -7,26 +7,25 @@ pub fn add_blank_lines<S: Into<String> + AsRef<str>>(code: S, blank_lines: usize
 
     let mut parts = vec![];
 
-    // Let's skip straight for the struct block.
-    let (first, rest) = code
-        .as_ref()
-        .split_once('{')
-        .expect("There must be struct block opening braces.");
+    // Let's skip straight for the struct block, leaving anything without one as it is.
+    let Some((first, rest)) = code.as_ref().split_once('{') else {
+        return code.into();
+    };
     parts.push(first);
     parts.push("{");
 
     // Let's take care of the end as well.
-    let (rest, last) = rest
-        .rsplit_once('}')
-        .expect("There must be struct block closing braces.");
+    let Some((rest, last)) = rest.rsplit_once('}') else {
+        return code.into();
+    };
 
-    // Split the struct fields using the trailing comma.
-    let mut iter = rest.split_inclusive(',').peekable();
+    // Split the struct fields using the trailing comma, ignoring commas inside types.
+    let mut iter = rest.split_inclusive(",\n").peekable();
 
     while let Some(s) = iter.next() {
         parts.push(s);
 
-        if iter.peek().is_some_and(|s| s.ends_with(',')) {
+        if iter.peek().is_some_and(|s| s.ends_with(",\n")) {
             parts.push(&replacement_separator);
         }
     }
@@ -102,4 +101,23 @@ mod tests {
         }"#}
         );
     }
+
+    #[test]
+    fn blank_lines_with_generics() {
+        let code = indoc! {r#"
+            pub struct SomeStruct {
+                pub field_a: HashMap<String, String>,
+                pub field_b: i32,
+            }"#};
+
+        assert_eq!(
+            add_blank_lines(code, 1),
+            indoc! {r#"
+            pub struct SomeStruct {
+                pub field_a: HashMap<String, String>,
+
+                pub field_b: i32,
+            }"#}
+        );
+    }
 }
@@ -0,0 +1,199 @@
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+
+use crate::{field::Field, type_parser::TypeParser};
+
+/// Integers beyond this can't be told apart from their neighbours as `f64`,
+/// so the observed range may not hold them exactly.
+const EXACT_INTEGERS: f64 = (1u64 << f64::MANTISSA_DIGITS) as f64;
+
+/// The error returned by generated `validate` methods, and its impls.
+pub fn error_type(vis: &TokenStream) -> Vec<TokenStream> {
+    vec![
+        quote! {
+            #[derive(Debug)]
+            #vis struct ValidationError {
+                #vis column: &'static str,
+                #vis message: String,
+            }
+        },
+        quote! {
+            impl std::fmt::Display for ValidationError {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    write!(f, "{}: {}", self.column, self.message)
+                }
+            }
+        },
+        quote! {
+            impl std::error::Error for ValidationError {}
+        },
+    ]
+}
+
+/// A `validate` method checking the fields of a struct against the values
+/// they were inferred from, and validating flattened structs in turn.
+pub fn for_struct(
+    vis: &TokenStream,
+    struct_name: &syn::Ident,
+    generics: TokenStream,
+    fields: &[Field],
+) -> TokenStream {
+    let checks = fields.iter().filter_map(|f| {
+        let field_name = syn::parse_str::<syn::Ident>(&f.name).ok()?;
+
+        if f.is_flattened() {
+            return Some(quote! {self.#field_name.validate()?;});
+        }
+
+        let check = check(f)?;
+        Some(if f.is_option() {
+            quote! {
+                if let Some(value) = &self.#field_name {
+                    #check
+                }
+            }
+        } else {
+            quote! {
+                let value = &self.#field_name;
+                #check
+            }
+        })
+    });
+
+    quote! {
+        impl #struct_name #generics {
+            #vis fn validate(&self) -> Result<(), ValidationError> {
+                #(#checks)*
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A `validate` method validating whichever struct a tagged enum holds.
+pub fn for_enum(
+    vis: &TokenStream,
+    enum_name: &syn::Ident,
+    generics: TokenStream,
+    variants: &[syn::Ident],
+) -> TokenStream {
+    quote! {
+        impl #enum_name #generics {
+            #vis fn validate(&self) -> Result<(), ValidationError> {
+                match self {
+                    #(#enum_name::#variants(record) => record.validate(),)*
+                }
+            }
+        }
+    }
+}
+
+/// Checks on `value`, a reference to a field's value, from the statistics
+/// collected for it. Fields of other types than the inferred ones are taken
+/// as they are.
+fn check(field: &Field) -> Option<TokenStream> {
+    let stats = field.stats.as_ref()?;
+    let parser = field.builtin_parser()?;
+    let column = &field.raw_name;
+
+    let fail = |message: TokenStream| {
+        quote! {
+            return Err(ValidationError {
+                column: #column,
+                message: #message,
+            });
+        }
+    };
+
+    if parser.is_numeric() {
+        let (min, max) = (stats.min?, stats.max?);
+        let is_float = matches!(parser, TypeParser::F32 | TypeParser::F64);
+
+        let literal = |n: f64| {
+            if !n.is_finite() {
+                None
+            } else if is_float {
+                Some(format!("{n:?}"))
+            } else if n.abs() <= EXACT_INTEGERS {
+                Some(format!("{n:.0}"))
+            } else {
+                None
+            }
+        };
+        let (min, max) = (literal(min)?, literal(max)?);
+
+        let message = format!("{{value}} is not between {min} and {max}");
+        let fail = fail(quote! {format!(#message)});
+        let (min, max) = (
+            min.parse::<TokenStream>().ok()?,
+            max.parse::<TokenStream>().ok()?,
+        );
+        return Some(quote! {
+            if !(#min..=#max).contains(value) {
+                #fail
+            }
+        });
+    }
+
+    if parser != TypeParser::String {
+        return None;
+    }
+
+    let text = if field.is_borrowed() {
+        quote! {*value}
+    } else {
+        quote! {value.as_str()}
+    };
+
+    match stats.categories() {
+        Some(values) => {
+            let listed = values
+                .iter()
+                .map(|v| format!("{v:?}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+                .replace('{', "{{")
+                .replace('}', "}}");
+            let message = format!("{{value:?}} is not one of {listed}");
+            let fail = fail(quote! {format!(#message)});
+            let values = values.iter();
+
+            Some(quote! {
+                if !matches!(#text, #(#values)|*) {
+                    #fail
+                }
+            })
+        }
+        _ => {
+            let max_len = stats.max_len?;
+            let message = format!("{{value:?}} is longer than {max_len} characters");
+            let fail = fail(quote! {format!(#message)});
+            let max_len = Literal::usize_unsuffixed(max_len);
+
+            Some(quote! {
+                if value.chars().count() > #max_len {
+                    #fail
+                }
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{run_on_str, Config};
+
+    #[test]
+    fn checks() {
+        let config = Config::builder().with_validation(true).build();
+        let code = run_on_str("id,status,note\n1,open,a\n4,done,abc\n2,open,\n", &config).unwrap();
+
+        assert!(code.contains("if !(1..=4).contains(value) {"), "{code}");
+        assert!(code.contains(r#"if !matches!(value.as_str(), "done" | "open") {"#));
+        assert!(code.contains("if let Some(value) = &self.note {"));
+        assert!(code.contains("if value.chars().count() > 3 {"));
+        assert!(code.contains("pub struct ValidationError {"));
+        // Statistics are collected for the checks, not to document fields.
+        assert!(!code.contains("/// Samples:"));
+    }
+}
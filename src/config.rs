@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+
+use crate::{
+    Emit, EmptyColumns, ExtraColumns, FieldCase, RowFilter, Sampling, SqlDialect, Trim, TypeInfer,
+    TypeRegistry, Visibility,
+};
+
+/// Declares the fields of [`Config`] with their defaults, and a
+/// [`ConfigBuilder`] setter for each.
+macro_rules! config {
+    ($($(#[$doc:meta])* $name:ident: $type:ty = $default:expr,)*) => {
+        /// Options for inference and code generation. Start from
+        /// [`Config::builder`] or [`Config::default`], as fields are added
+        /// over time.
+        #[derive(Clone, Debug)]
+        #[non_exhaustive]
+        pub struct Config {
+            $($(#[$doc])* pub $name: $type,)*
+        }
+
+        impl Default for Config {
+            fn default() -> Self {
+                Config {
+                    $($name: $default,)*
+                }
+            }
+        }
+
+        impl ConfigBuilder {
+            $(
+                $(#[$doc])*
+                pub fn $name(mut self, $name: impl Into<$type>) -> Self {
+                    self.config.$name = $name.into();
+                    self
+                }
+            )*
+        }
+    };
+}
+
+config! {
+    /// Number of records to infer types from.
+    lines: usize = usize::MAX,
+    /// Skip records with no more than this many non-empty fields.
+    min_fields: usize = 0,
+    /// Skip records with more than this many non-empty fields.
+    max_fields: Option<usize> = None,
+    /// Skip records matching this pattern, matched against their fields
+    /// joined with commas.
+    skip_matching: Option<regex::Regex> = None,
+    /// Name of the generated type, for a single input.
+    struct_name: String = "Record".to_string(),
+    /// Blank lines between the fields of generated structs.
+    blank_lines: usize = 1,
+    /// Infer `HashMap<String, String>` for cells like `a=1;b=2`.
+    map_cells: bool = false,
+    /// Infer `Duration` for cells like `1h30m`.
+    durations: bool = false,
+    /// Infer `PathBuf` for cells that look like file paths.
+    path_type: bool = false,
+    /// Domain-specific types to infer, in preference to the built-in ones.
+    custom_types: TypeRegistry = TypeRegistry::new(),
+    /// Types to use instead of the inferred ones, by column.
+    type_overrides: HashMap<String, String> = HashMap::new(),
+    /// Columns to make optional, whatever was seen.
+    optional_columns: Vec<String> = vec![],
+    /// Columns never to make optional, whatever was seen.
+    required_columns: Vec<String> = vec![],
+    /// Cells standing for a missing value, like `NA`, counted as empty ones.
+    null_values: Vec<String> = vec![],
+    /// Columns whose values are masked as they are deserialized, keeping only
+    /// their length.
+    redact_columns: Vec<String> = vec![],
+    /// Make every column optional, except the required ones.
+    all_optional: bool = false,
+    /// Fraction of empty cells a field tolerates before becoming optional.
+    optional_threshold: f64 = 0.0,
+    /// How to generate columns for which no values were seen.
+    empty_columns: EmptyColumns = Default::default(),
+    /// Columns to leave out of generated types.
+    skip_columns: Vec<String> = vec![],
+    /// What to do with fields beyond the header row.
+    extra_columns: ExtraColumns = Default::default(),
+    /// Field names replacing the file's header row, or naming the columns of
+    /// a file without one.
+    headers: Vec<String> = vec![],
+    /// Names columns whose header leaves nothing to name a field after,
+    /// followed by the column's index.
+    placeholder_prefix: String = "column_".to_string(),
+    /// Character used for quoting fields.
+    quote: u8 = b'"',
+    /// Character escaping quotes, instead of doubling them.
+    escape: Option<u8> = None,
+    /// Whether doubled quotes in quoted fields stand for a single one.
+    double_quote: bool = true,
+    /// Skip lines starting with this character.
+    comment: Option<u8> = None,
+    /// Accept records with a varying number of fields.
+    flexible: bool = false,
+    /// Where leading and trailing whitespace is trimmed.
+    trim: Trim = Default::default(),
+    /// Which records take part in inference.
+    sample: Sampling = Default::default(),
+    /// Number of records to drop from the end of the input.
+    skip_footer: usize = 0,
+    /// Trailing records matching this pattern are dropped too.
+    footer_pattern: Option<regex::Regex> = None,
+    /// Only rows matching all of these take part in inference.
+    filters: Vec<RowFilter> = vec![],
+    /// Stop reading once no field has changed for this many consecutive rows.
+    converge: Option<usize> = None,
+    /// Threads narrowing types over batches of records, or 0 for one per CPU.
+    /// Ignored with `converge`, which needs rows in order.
+    jobs: usize = 1,
+    /// Generate a struct per value of this column, and a tagged enum of them.
+    split_by: Option<String> = None,
+    /// Traits to derive in addition to `Debug` and `Deserialize`.
+    derives: Vec<String> = vec![],
+    /// Visibility of the generated types and their fields.
+    visibility: Visibility = Default::default(),
+    /// Naming convention of the generated field names.
+    field_case: FieldCase = Default::default(),
+    /// Prepended to field names that would start with a digit.
+    digit_prefix: String = "n_".to_string(),
+    /// Keep non-ASCII letters in field names, rather than transliterating them.
+    allow_unicode_idents: bool = false,
+    /// Document each field with statistics about the values seen.
+    stats_docs: bool = false,
+    /// Add `#[serde(default)]` to optional fields.
+    serde_default: bool = false,
+    /// Skip serializing optional fields that are `None`. Ignored with
+    /// `with_writer`, as CSV rows need a cell for every column.
+    skip_serializing_none: bool = false,
+    /// Leave out `#[serde(rename)]` attributes, documenting each field's
+    /// column instead, for headers matching the field names or records read
+    /// by position.
+    no_rename: bool = false,
+    /// Order struct fields by name rather than by column. Records then only
+    /// deserialize by header, so this is ignored without headers or renames.
+    sort_fields: bool = false,
+    /// Generate `&'a str` instead of `String`, borrowing from the input.
+    /// Ignored with `split_by`, whose variants are read from a copy of each
+    /// record.
+    borrow: bool = false,
+    /// Group columns sharing a prefix before this separator into nested structs.
+    nest_separator: Option<String> = None,
+    /// Generate a function reading a whole file into the generated type.
+    with_reader: bool = false,
+    /// Generate a function iterating over the records of a file.
+    with_iter: bool = false,
+    /// Generate a function writing records of the generated type to a file,
+    /// deriving `Serialize` for it. Skipped for types with nested structs.
+    with_writer: bool = false,
+    /// Generate a `validate` method checking records against the ranges,
+    /// lengths and categories of the values seen.
+    with_validation: bool = false,
+    /// Generate a test deserializing the first few records into the generated type.
+    with_tests: bool = false,
+    /// Start the output with `use` statements for the types it refers to.
+    with_imports: bool = true,
+    /// Format of the generated output.
+    emit: Emit = Default::default(),
+    /// Dialect of generated SQL.
+    sql_dialect: SqlDialect = Default::default(),
+    /// Also generate a function converting the CSV file to Parquet, with `--emit arrow`.
+    parquet_example: bool = false,
+    /// Also generate an `INSERT` statement, with `--emit sqlx`.
+    insert_template: bool = false,
+    /// Column holding the primary key of `--emit diesel` tables. By default,
+    /// `id`, or else the first column whose values were all distinct.
+    primary_key: Option<String> = None,
+    /// Delimiter of the input, and of generated reader functions.
+    delimiter: u8 = b',',
+    /// Whether the input, and so generated reader functions, start with a header row.
+    has_headers: bool = true,
+}
+
+/// Builds a [`Config`], starting from its defaults.
+#[derive(Clone, Debug, Default)]
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl ConfigBuilder {
+    /// Adds a custom type to [`Config::custom_types`].
+    pub fn register_type(mut self, type_infer: impl TypeInfer + 'static) -> Self {
+        self.config.custom_types.register(type_infer);
+        self
+    }
+
+    pub fn build(self) -> Config {
+        self.config
+    }
+}
+
+impl Config {
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+
+    /// A reader for input in the dialect this config describes.
+    pub fn reader_builder(&self) -> csv::ReaderBuilder {
+        let mut builder = csv::ReaderBuilder::new();
+        builder
+            .delimiter(self.delimiter)
+            .has_headers(self.has_headers)
+            .quote(self.quote)
+            .escape(self.escape)
+            .double_quote(self.double_quote)
+            .comment(self.comment)
+            .flexible(self.flexible)
+            .trim(self.trim.into());
+        builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::Config;
+    use crate::run_on_str;
+
+    #[test]
+    fn builder() {
+        let config = Config::builder()
+            .struct_name("Order")
+            .delimiter(b';')
+            .with_imports(false)
+            .build();
+        let csv = indoc! {"
+            id;total;note
+            1;9.5;
+            2;12;gift
+        "};
+
+        assert_eq!(
+            run_on_str(csv, &config).unwrap(),
+            indoc! {"
+                #[derive(Debug, Deserialize)]
+                pub struct Order {
+                    pub id: u8,
+
+                    pub total: f32,
+
+                    pub note: Option<String>,
+                }
+            "}
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{Field, Table};
+
+/// How a column changed between two versions of a table.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum Change {
+    Added {
+        column: String,
+        #[serde(rename = "type")]
+        type_name: String,
+    },
+    Removed {
+        column: String,
+        #[serde(rename = "type")]
+        type_name: String,
+    },
+    Retyped {
+        column: String,
+        old: String,
+        new: String,
+    },
+    /// The column became optional, or stopped being so.
+    Nullability { column: String, optional: bool },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Added { column, type_name } => write!(f, "+ {column}: {type_name}"),
+            Change::Removed { column, type_name } => write!(f, "- {column}: {type_name}"),
+            Change::Retyped { column, old, new } => write!(f, "~ {column}: {old} -> {new}"),
+            Change::Nullability { column, optional } => {
+                let now = if *optional { "optional" } else { "required" };
+                write!(f, "~ {column}: now {now}")
+            }
+        }
+    }
+}
+
+/// The changes from `old` to `new`, matching columns by header. Removed
+/// columns come first, then the others in the order of `new`.
+pub fn diff(old: &Table, new: &Table) -> Vec<Change> {
+    let old = old.fields();
+    let new = new.fields();
+    let find = |fields: &[Field], name: &str| fields.iter().position(|f| f.raw_name == name);
+
+    let mut changes = old
+        .iter()
+        .filter(|f| find(&new, &f.raw_name).is_none())
+        .map(|f| Change::Removed {
+            column: f.raw_name.clone(),
+            type_name: f.type_name(),
+        })
+        .collect::<Vec<_>>();
+
+    for field in &new {
+        let column = field.raw_name.clone();
+
+        let Some(i) = find(&old, &field.raw_name) else {
+            changes.push(Change::Added {
+                column,
+                type_name: field.type_name(),
+            });
+            continue;
+        };
+
+        let (old_type, new_type) = (base_type(&old[i]), base_type(field));
+        if old_type != new_type {
+            changes.push(Change::Retyped {
+                column: column.clone(),
+                old: old_type,
+                new: new_type,
+            });
+        }
+
+        if old[i].is_option() != field.is_option() {
+            changes.push(Change::Nullability {
+                column,
+                optional: field.is_option(),
+            });
+        }
+    }
+
+    changes
+}
+
+/// The field's type, without the `Option` around optional ones.
+fn base_type(field: &Field) -> String {
+    let type_name = field.type_name();
+
+    match type_name
+        .strip_prefix("Option<")
+        .and_then(|t| t.strip_suffix('>'))
+    {
+        Some(inner) => inner.to_string(),
+        None => type_name,
+    }
+}
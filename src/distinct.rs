@@ -0,0 +1,191 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+/// Values counted exactly before switching to an estimate.
+const EXACT_LIMIT: usize = 1000;
+
+/// Bits of the hash picking a register, for 4096 registers and an error of
+/// about 1.6%.
+const PRECISION: u32 = 12;
+const REGISTERS: usize = 1 << PRECISION;
+
+/// Counts distinct values in bounded memory: exactly up to [`EXACT_LIMIT`],
+/// then estimated with HyperLogLog.
+///
+/// Saved as a list of either the values or the estimate's registers, so that
+/// schemas saved with a plain list of values still load.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Distinct {
+    Exact(BTreeSet<String>),
+    Estimated(Vec<u8>),
+}
+
+impl Default for Distinct {
+    fn default() -> Self {
+        Distinct::Exact(BTreeSet::new())
+    }
+}
+
+impl Distinct {
+    /// Adds a value, returning whether it was new, as far as can be told.
+    pub fn insert(&mut self, value: &str) -> bool {
+        match self {
+            Distinct::Exact(values) => {
+                if values.contains(value) {
+                    return false;
+                }
+                values.insert(value.to_string());
+
+                if values.len() > EXACT_LIMIT {
+                    *self = Distinct::Estimated(estimate(values.iter()));
+                }
+                true
+            }
+            Distinct::Estimated(registers) => add(registers, value),
+        }
+    }
+
+    pub fn merge(&mut self, other: &Distinct) {
+        match other {
+            Distinct::Exact(values) => {
+                for value in values {
+                    self.insert(value);
+                }
+            }
+            Distinct::Estimated(other) => {
+                if let Distinct::Exact(values) = self {
+                    *self = Distinct::Estimated(estimate(values.iter()));
+                }
+                if let Distinct::Estimated(registers) = self {
+                    for (register, other) in registers.iter_mut().zip(other) {
+                        *register = (*register).max(*other);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The number of distinct values.
+    pub fn len(&self) -> usize {
+        match self {
+            Distinct::Exact(values) => values.len(),
+            Distinct::Estimated(registers) => count(registers),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The values themselves, while they are counted exactly.
+    pub fn values(&self) -> Option<&BTreeSet<String>> {
+        match self {
+            Distinct::Exact(values) => Some(values),
+            Distinct::Estimated(_) => None,
+        }
+    }
+
+    pub fn is_estimated(&self) -> bool {
+        matches!(self, Distinct::Estimated(_))
+    }
+}
+
+fn estimate<'a>(values: impl Iterator<Item = &'a String>) -> Vec<u8> {
+    let mut registers = vec![0; REGISTERS];
+    for value in values {
+        add(&mut registers, value);
+    }
+    registers
+}
+
+/// Records a value's hash, returning whether that changed the estimate.
+fn add(registers: &mut [u8], value: &str) -> bool {
+    let hash = hash(value);
+    let index = (hash >> (64 - PRECISION)) as usize;
+    let rank = ((hash << PRECISION) | 1 << (PRECISION - 1)).leading_zeros() as u8 + 1;
+
+    let changed = rank > registers[index];
+    registers[index] = registers[index].max(rank);
+    changed
+}
+
+/// FNV-1a, followed by MurmurHash3's finalizer to spread its bits. Registers
+/// are saved in schemas, so unlike std's hasher this must never change.
+fn hash(value: &str) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325u64;
+    for byte in value.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    hash ^ (hash >> 33)
+}
+
+fn count(registers: &[u8]) -> usize {
+    let m = registers.len() as f64;
+    let alpha = 0.7213 / (1.0 + 1.079 / m);
+    let sum = registers
+        .iter()
+        .map(|&r| 2f64.powi(-(r as i32)))
+        .sum::<f64>();
+    let raw = alpha * m * m / sum;
+
+    // Linear counting is more accurate while many registers are unset.
+    let zeros = registers.iter().filter(|&&r| r == 0).count();
+    let estimate = if raw <= 2.5 * m && zeros > 0 {
+        m * (m / zeros as f64).ln()
+    } else {
+        raw
+    };
+
+    estimate.round() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hash, Distinct, EXACT_LIMIT};
+
+    #[test]
+    fn estimate() {
+        let mut distinct = Distinct::default();
+        for i in 0..EXACT_LIMIT {
+            distinct.insert(&i.to_string());
+            distinct.insert(&i.to_string());
+        }
+        assert_eq!(distinct.len(), EXACT_LIMIT);
+        assert!(!distinct.is_estimated());
+
+        for i in 0..100_000 {
+            distinct.insert(&(i % 50_000).to_string());
+        }
+        assert!(distinct.is_estimated());
+        assert!(
+            (48_000..52_000).contains(&distinct.len()),
+            "{}",
+            distinct.len()
+        );
+
+        let mut other = Distinct::default();
+        for i in 25_000..75_000 {
+            other.insert(&i.to_string());
+        }
+        distinct.merge(&other);
+        assert!(
+            (73_000..77_000).contains(&distinct.len()),
+            "{}",
+            distinct.len()
+        );
+    }
+
+    #[test]
+    fn stable_hash() {
+        assert_eq!(hash(""), 0xefd01f60ba992926);
+        assert_eq!(hash("csv2serde"), 0xdc185e4331ecb4b4);
+    }
+}
@@ -0,0 +1,191 @@
+mod arrow;
+mod avro;
+mod diesel;
+mod json_schema;
+mod polars;
+mod proto;
+mod python;
+mod sql;
+mod sqlx;
+mod typescript;
+
+use crate::{
+    codegen::RustStructs, field::Field, type_parser::TypeParser, Config, Emit, EmptyColumns, Error,
+    Schema,
+};
+
+/// Renders an inferred [`Schema`] as source code. Implement this to generate
+/// formats this crate doesn't support, and pass it to
+/// [`run_with`](crate::run_with).
+pub trait Emitter {
+    fn emit(&self, schema: &Schema, config: &Config) -> Result<String, Error>;
+}
+
+/// The built-in formats, with Rust structs rendered by [`RustStructs`].
+impl Emitter for Emit {
+    fn emit(&self, schema: &Schema, config: &Config) -> Result<String, Error> {
+        if *self == Emit::Rust {
+            RustStructs.emit(schema, config)
+        } else {
+            generate(*self, config, schema)
+        }
+    }
+}
+
+/// Renders the inferred tables in a format other than Rust. Each table is
+/// named after its input, and has the fields of all its groups merged.
+fn generate(emit: Emit, config: &Config, schema: &Schema) -> Result<String, Error> {
+    let tables = schema
+        .tables
+        .iter()
+        .map(|table| {
+            let name = table.name.clone();
+            let mut fields = table.fields();
+            fields.retain(|f| !f.is_listed(&config.skip_columns));
+
+            if config.empty_columns == EmptyColumns::Skip {
+                fields.retain(|f| !f.is_empty());
+            }
+
+            (name, fields)
+        })
+        .collect::<Vec<_>>();
+
+    let items = match emit {
+        Emit::Rust => unreachable!("Rust output is generated by RustStructs."),
+        Emit::Sql => tables
+            .iter()
+            .map(|(name, fields)| sql::generate(config.sql_dialect, name, fields))
+            .collect::<Vec<_>>(),
+        Emit::JsonSchema => vec![json_schema::generate(&tables)],
+        Emit::Arrow => tables
+            .iter()
+            .zip(&schema.tables)
+            .map(|((name, fields), table)| arrow::generate(&table.dialect(config), name, fields))
+            .collect::<Result<Vec<_>, Error>>()?,
+        Emit::Polars => tables
+            .iter()
+            .zip(&schema.tables)
+            .map(|((name, fields), table)| polars::generate(&table.dialect(config), name, fields))
+            .collect::<Result<Vec<_>, Error>>()?,
+        Emit::Diesel => tables
+            .iter()
+            .map(|(name, fields)| diesel::generate(config, name, fields))
+            .collect::<Result<Vec<_>, Error>>()?,
+        Emit::Sqlx => tables
+            .iter()
+            .map(|(name, fields)| sqlx::generate(config, name, fields))
+            .collect::<Result<Vec<_>, Error>>()?,
+        Emit::Typescript => tables
+            .iter()
+            .map(|(name, fields)| typescript::generate(name, fields))
+            .collect::<Vec<_>>(),
+        Emit::Pydantic => vec![python::generate(python::Kind::Pydantic, &tables)],
+        Emit::Dataclass => vec![python::generate(python::Kind::Dataclass, &tables)],
+        Emit::Proto => vec![proto::generate(&tables)],
+        Emit::Avro => vec![avro::generate(&tables)],
+    };
+
+    Ok(items.join("\n"))
+}
+
+/// A field name without the raw identifier prefix Rust needs for keywords.
+fn plain_name(field: &Field) -> &str {
+    field.name.trim_start_matches("r#")
+}
+
+/// The Rust type the field was given instead of the inferred one: by
+/// [`Config::type_overrides`], a custom type, or `String` for masked values.
+fn declared_type(field: &Field) -> Option<String> {
+    if field.is_redacted() {
+        return Some("String".to_string());
+    }
+    field
+        .type_override()
+        .map(str::to_string)
+        .or_else(|| field.custom_type())
+}
+
+/// The inferred type, or the built-in one matching the declared type, so
+/// that other formats agree with the Rust struct. Declared types that aren't
+/// built in are taken as text, unless the format knows them.
+fn parser(field: &Field) -> Option<TypeParser> {
+    let Some(declared) = declared_type(field) else {
+        return field.type_parser();
+    };
+
+    let name = last_segment(&declared);
+    let parser = TypeParser::all()
+        .into_iter()
+        .find(|p| last_segment(p.type_name(false)) == name)
+        .unwrap_or(TypeParser::String);
+    Some(parser)
+}
+
+/// The type name of a possibly qualified, possibly generic type, like
+/// `NaiveDate` for `chrono::NaiveDate`.
+fn last_segment(path: &str) -> &str {
+    let path = path.split('<').next().unwrap_or(path);
+    path.rsplit("::").next().unwrap_or(path).trim()
+}
+
+/// The Rust type holding values of the inferred type that database drivers
+/// support: signed integers wide enough for unsigned ones, and text for
+/// everything that doesn't fit.
+fn sql_rust_type(parser: Option<TypeParser>) -> &'static str {
+    use TypeParser::*;
+
+    match parser {
+        Some(U8 | I8 | I16) => "i16",
+        Some(U16 | I32) => "i32",
+        Some(U32 | I64) => "i64",
+        Some(F32) => "f32",
+        Some(F64) => "f64",
+        Some(U64 | U128 | I128 | Duration | Map | Char | Path | String) | None => "String",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use indoc::indoc;
+    use proc_macro2::TokenStream;
+    use quote::quote;
+
+    use crate::{run_on_str, Config, Emit, TypeInfer};
+
+    struct Sku;
+
+    impl TypeInfer for Sku {
+        fn try_parse(&self, value: &str) -> bool {
+            value.len() == 4 && value.bytes().all(|b| b.is_ascii_digit())
+        }
+
+        fn type_tokens(&self) -> TokenStream {
+            quote! { sku::Sku }
+        }
+    }
+
+    /// A table in `emit`'s format, with a custom type, an overridden type and
+    /// a masked column, each of which the format has to agree with Rust on.
+    pub(super) fn sample(emit: Emit) -> String {
+        let config = Config::builder()
+            .emit(emit)
+            .struct_name("Order")
+            .register_type(Sku)
+            .type_overrides(HashMap::from([(
+                "created".to_string(),
+                "chrono::NaiveDate".to_string(),
+            )]))
+            .redact_columns(vec!["secret".to_string()])
+            .build();
+        let csv = indoc! {"
+            sku,total,created,secret,note
+            1001,9.5,2024-01-02,123,
+            1002,12,2024-01-03,456,x
+        "};
+
+        run_on_str(csv, &config).unwrap()
+    }
+}
@@ -0,0 +1,162 @@
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use super::{declared_type, last_segment, parser};
+use crate::{field::Field, type_parser::TypeParser, Config, Error};
+
+/// A function building the `arrow` schema of the table, and optionally one
+/// converting the CSV file into Parquet with it.
+pub fn generate(config: &Config, name: &str, fields: &[Field]) -> Result<String, Error> {
+    let snake_name = name.to_case(Case::Snake);
+    let schema_fn = format_ident!("{snake_name}_schema");
+
+    let fields = fields
+        .iter()
+        .map(|f| {
+            let name = &f.raw_name;
+            let data_type = data_type(f);
+            let nullable = f.is_option();
+            quote! {
+                Field::new(#name, #data_type, #nullable)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let time_unit = fields
+        .iter()
+        .any(|f| f.to_string().contains("TimeUnit"))
+        .then(|| quote! {, TimeUnit});
+
+    let mut items = vec![quote! {
+        pub fn #schema_fn() -> arrow::datatypes::Schema {
+            use arrow::datatypes::{DataType, Field, Schema #time_unit};
+
+            Schema::new(Vec::from([#(#fields),*]))
+        }
+    }];
+
+    if config.parquet_example {
+        let writer_fn = format_ident!("write_{snake_name}_parquet");
+        let delimiter = format!("b'{}'", config.delimiter.escape_ascii())
+            .parse::<TokenStream>()
+            .unwrap();
+        let has_headers = config.has_headers;
+
+        items.push(quote! {
+            pub fn #writer_fn(
+                csv_path: impl AsRef<std::path::Path>,
+                parquet_path: impl AsRef<std::path::Path>,
+            ) -> Result<(), Box<dyn std::error::Error>> {
+                let schema = std::sync::Arc::new(#schema_fn());
+                let reader = arrow::csv::ReaderBuilder::new(schema.clone())
+                    .with_header(#has_headers)
+                    .with_delimiter(#delimiter)
+                    .build(std::fs::File::open(csv_path)?)?;
+
+                let file = std::fs::File::create(parquet_path)?;
+                let mut writer = parquet::arrow::ArrowWriter::try_new(file, schema, None)?;
+                for batch in reader {
+                    writer.write(&batch?)?;
+                }
+
+                writer.close()?;
+                Ok(())
+            }
+        });
+    }
+
+    let items = items
+        .into_iter()
+        .map(|item| {
+            let syntax_tree = syn::parse2(item).map_err(Error::CantGenerateCode)?;
+            Ok(prettyplease::unparse(&syntax_tree))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(items.join("\n"))
+}
+
+fn data_type(field: &Field) -> TokenStream {
+    use TypeParser::*;
+
+    let declared = declared_type(field);
+    match declared.as_deref().map(last_segment) {
+        Some("NaiveDate") => return quote! {DataType::Date32},
+        Some("NaiveTime") => {
+            return quote! {
+                DataType::Time64(TimeUnit::Microsecond)
+            }
+        }
+        Some("NaiveDateTime" | "DateTime") => {
+            return quote! {
+                DataType::Timestamp(TimeUnit::Microsecond, None)
+            }
+        }
+        _ => {}
+    }
+
+    let Some(parser) = parser(field) else {
+        return quote! {DataType::Null};
+    };
+
+    let data_type = match parser {
+        U8 => quote! {UInt8},
+        U16 => quote! {UInt16},
+        U32 => quote! {UInt32},
+        U64 => quote! {UInt64},
+        I8 => quote! {Int8},
+        I16 => quote! {Int16},
+        I32 => quote! {Int32},
+        I64 => quote! {Int64},
+        // Arrow has no 128-bit integers; decimals hold all but the largest `u128`s.
+        U128 | I128 => quote! {Decimal128(38, 0)},
+        F32 => quote! {Float32},
+        F64 => quote! {Float64},
+        Duration => quote! {Duration(TimeUnit::Millisecond)},
+        Map => quote! {
+            Map(
+                std::sync::Arc::new(Field::new_struct(
+                    "entries",
+                    Vec::from([
+                        Field::new("keys", DataType::Utf8, false),
+                        Field::new("values", DataType::Utf8, true),
+                    ]),
+                    false,
+                )),
+                false,
+            )
+        },
+        Char | Path | String => quote! {Utf8},
+    };
+
+    quote! {DataType::#data_type}
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use crate::{emit::tests::sample, Emit};
+
+    #[test]
+    fn declared_types() {
+        assert_eq!(
+            sample(Emit::Arrow),
+            indoc! {r#"
+                pub fn order_schema() -> arrow::datatypes::Schema {
+                    use arrow::datatypes::{DataType, Field, Schema};
+                    Schema::new(
+                        Vec::from([
+                            Field::new("sku", DataType::Utf8, false),
+                            Field::new("total", DataType::Float32, false),
+                            Field::new("created", DataType::Date32, false),
+                            Field::new("secret", DataType::Utf8, false),
+                            Field::new("note", DataType::Utf8, true),
+                        ]),
+                    )
+                }
+            "#}
+        );
+    }
+}
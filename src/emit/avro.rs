@@ -0,0 +1,112 @@
+use serde_json::{json, Value};
+
+use super::{declared_type, last_segment, parser, plain_name};
+use crate::{field::Field, type_parser::TypeParser};
+
+/// An Avro record schema per table, or a union of them for several tables.
+pub fn generate(tables: &[(String, Vec<Field>)]) -> String {
+    let records = tables
+        .iter()
+        .map(|(name, fields)| record(name, fields))
+        .collect::<Vec<_>>();
+
+    let document = match <[Value; 1]>::try_from(records) {
+        Ok([record]) => record,
+        Err(records) => Value::Array(records),
+    };
+
+    serde_json::to_string_pretty(&document).expect("Schemas are valid JSON.") + "\n"
+}
+
+fn record(name: &str, fields: &[Field]) -> Value {
+    let fields = fields
+        .iter()
+        .map(|f| {
+            let field_type = field_type(f);
+
+            // Optional columns are unions with `null` first, so that it can be the default.
+            if f.is_option() && field_type != json!("null") {
+                json!({ "name": plain_name(f), "type": ["null", field_type], "default": null })
+            } else {
+                json!({ "name": plain_name(f), "type": field_type })
+            }
+        })
+        .collect::<Vec<_>>();
+
+    json!({ "type": "record", "name": name, "fields": fields })
+}
+
+/// 64-bit unsigned and 128-bit integers don't fit a `long`, and are kept as strings.
+fn field_type(field: &Field) -> Value {
+    use TypeParser::*;
+
+    match declared_type(field).as_deref().map(last_segment) {
+        Some("NaiveDate") => return json!({ "type": "int", "logicalType": "date" }),
+        Some("NaiveTime") => return json!({ "type": "long", "logicalType": "time-micros" }),
+        Some("NaiveDateTime") => {
+            return json!({ "type": "long", "logicalType": "local-timestamp-micros" })
+        }
+        Some("DateTime") => return json!({ "type": "long", "logicalType": "timestamp-micros" }),
+        Some("Uuid") => return json!({ "type": "string", "logicalType": "uuid" }),
+        _ => {}
+    }
+
+    match parser(field) {
+        Some(U8 | U16 | I8 | I16 | I32) => json!("int"),
+        Some(U32 | I64) => json!("long"),
+        Some(F32) => json!("float"),
+        Some(F64) => json!("double"),
+        Some(Map) => json!({ "type": "map", "values": "string" }),
+        Some(U64 | U128 | I128 | Duration | Char | Path | String) => json!("string"),
+        None => json!("null"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use crate::{emit::tests::sample, Emit};
+
+    #[test]
+    fn declared_types() {
+        assert_eq!(
+            sample(Emit::Avro),
+            indoc! {r#"
+                {
+                  "type": "record",
+                  "name": "Order",
+                  "fields": [
+                    {
+                      "name": "sku",
+                      "type": "string"
+                    },
+                    {
+                      "name": "total",
+                      "type": "float"
+                    },
+                    {
+                      "name": "created",
+                      "type": {
+                        "type": "int",
+                        "logicalType": "date"
+                      }
+                    },
+                    {
+                      "name": "secret",
+                      "type": "string"
+                    },
+                    {
+                      "name": "note",
+                      "type": [
+                        "null",
+                        "string"
+                      ],
+                      "default": null
+                    }
+                  ]
+                }
+            "#}
+        );
+    }
+}
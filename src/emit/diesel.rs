@@ -0,0 +1,168 @@
+use convert_case::{Case, Casing};
+use quote::{format_ident, quote};
+
+use super::{parser, sql_rust_type};
+use crate::{field::Field, Config, Error};
+
+/// A `table!` invocation, and a struct to query and insert its rows.
+pub fn generate(config: &Config, name: &str, fields: &[Field]) -> Result<String, Error> {
+    let table_name = name.to_case(Case::Snake);
+
+    // Keywords can't be raw identifiers here, so the column gets renamed.
+    let columns = fields
+        .iter()
+        .map(|f| match f.name.strip_prefix("r#") {
+            Some(name) => (format!("{name}_"), Some(name)),
+            None => (f.name.clone(), None),
+        })
+        .collect::<Vec<_>>();
+
+    let key = primary_key(config, name, fields)?;
+    let primary_key = columns[key].0.as_str();
+
+    let mut table = format!("diesel::table! {{\n    {table_name} ({primary_key}) {{\n");
+    for ((column, sql_name), field) in columns.iter().zip(fields) {
+        if let Some(sql_name) = sql_name {
+            table.push_str(&format!("        #[sql_name = \"{sql_name}\"]\n"));
+        }
+
+        let sql_type = sql_type(sql_rust_type(parser(field)));
+        if field.is_option() {
+            table.push_str(&format!("        {column} -> Nullable<{sql_type}>,\n"));
+        } else {
+            table.push_str(&format!("        {column} -> {sql_type},\n"));
+        }
+    }
+    table.push_str("    }\n}\n");
+
+    let struct_name = format_ident!("{}", name);
+    let table_ident = format_ident!("{}", table_name);
+    let struct_fields = columns.iter().zip(fields).map(|((column, _), f)| {
+        let column = format_ident!("{}", column);
+        let rust_type = sql_rust_type(parser(f));
+        let rust_type = if f.is_option() {
+            format!("Option<{rust_type}>")
+        } else {
+            rust_type.to_string()
+        };
+        let rust_type = syn::Type::Verbatim(rust_type.parse().unwrap());
+
+        quote! {pub #column: #rust_type,}
+    });
+
+    let model = quote! {
+        #[derive(Debug, diesel::Queryable, diesel::Insertable)]
+        #[diesel(table_name = #table_ident)]
+        pub struct #struct_name {
+            #(#struct_fields)*
+        }
+    };
+
+    let syntax_tree = syn::parse2(model).map_err(Error::CantGenerateCode)?;
+    Ok(format!("{table}\n{}", prettyplease::unparse(&syntax_tree)))
+}
+
+/// Index of the column to be the primary key Diesel needs: the one given,
+/// `id`, or else the first required column whose values were all distinct.
+fn primary_key(config: &Config, name: &str, fields: &[Field]) -> Result<usize, Error> {
+    if let Some(key) = &config.primary_key {
+        return fields
+            .iter()
+            .position(|f| f.is_listed(std::slice::from_ref(key)))
+            .ok_or_else(|| Error::UnknownPrimaryKey(key.clone()));
+    }
+
+    let distinct = |f: &Field| {
+        f.stats.as_ref().is_some_and(|stats| {
+            !f.is_option()
+                && stats.values > 0
+                && !stats.distinct.is_estimated()
+                && stats.distinct.len() == stats.values
+        })
+    };
+
+    fields
+        .iter()
+        .position(|f| f.name == "id")
+        .or_else(|| fields.iter().position(distinct))
+        .ok_or_else(|| Error::NoPrimaryKey(name.to_string()))
+}
+
+/// The Diesel SQL type mapped to the given Rust type.
+fn sql_type(rust_type: &str) -> &'static str {
+    match rust_type {
+        "i16" => "SmallInt",
+        "i32" => "Integer",
+        "i64" => "BigInt",
+        "f32" => "Float",
+        "f64" => "Double",
+        _ => "Text",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use crate::{emit::tests::sample, run_on_str, Config, Emit, Error};
+
+    #[test]
+    fn declared_types() {
+        assert_eq!(
+            sample(Emit::Diesel),
+            indoc! {r"
+                diesel::table! {
+                    order (sku) {
+                        sku -> Text,
+                        total -> Float,
+                        created -> Text,
+                        secret -> Text,
+                        note -> Nullable<Text>,
+                    }
+                }
+
+                #[derive(Debug, diesel::Queryable, diesel::Insertable)]
+                #[diesel(table_name = order)]
+                pub struct Order {
+                    pub sku: String,
+                    pub total: f32,
+                    pub created: String,
+                    pub secret: String,
+                    pub note: Option<String>,
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn primary_key() {
+        let config = |key: Option<&str>| {
+            Config::builder()
+                .emit(Emit::Diesel)
+                .primary_key(key.map(str::to_string))
+                .build()
+        };
+        let key = |csv, key| {
+            let code = run_on_str(csv, &config(key))?;
+            let table = code.lines().nth(1).unwrap().trim();
+            Ok::<_, Error>(table.to_string())
+        };
+
+        let csv = "group,code,id\na,1,1\na,2,2\n";
+        assert_eq!(key(csv, None).unwrap(), "record (id) {");
+        assert_eq!(key(csv, Some("code")).unwrap(), "record (code) {");
+        assert!(matches!(
+            key(csv, Some("sku")),
+            Err(Error::UnknownPrimaryKey(name)) if name == "sku"
+        ));
+
+        let csv = "group,code,note\na,1,\na,2,x\n";
+        assert_eq!(key(csv, None).unwrap(), "record (code) {");
+
+        let csv = "group,code\na,1\na,1\n";
+        assert!(matches!(
+            key(csv, None),
+            Err(Error::NoPrimaryKey(name)) if name == "Record"
+        ));
+    }
+}
@@ -0,0 +1,148 @@
+use serde_json::{json, Map, Value};
+
+use super::{declared_type, last_segment, parser};
+use crate::{field::Field, type_parser::TypeParser};
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// A JSON Schema document describing a row of each table. A single table is
+/// described at the top level, several are put under `$defs`.
+pub fn generate(tables: &[(String, Vec<Field>)]) -> String {
+    let document = match tables {
+        [(name, fields)] => {
+            let mut schema = Map::from_iter([("$schema".to_string(), json!(DRAFT))]);
+            schema.extend(object_schema(name, fields));
+            Value::Object(schema)
+        }
+        _ => {
+            let defs = tables
+                .iter()
+                .map(|(name, fields)| (name.clone(), Value::Object(object_schema(name, fields))))
+                .collect::<Map<_, _>>();
+            json!({ "$schema": DRAFT, "$defs": defs })
+        }
+    };
+
+    serde_json::to_string_pretty(&document).expect("Schemas are valid JSON.") + "\n"
+}
+
+fn object_schema(name: &str, fields: &[Field]) -> Map<String, Value> {
+    let properties = fields
+        .iter()
+        .map(|f| (f.raw_name.clone(), Value::Object(property(f))))
+        .collect::<Map<_, _>>();
+
+    let required = fields
+        .iter()
+        .filter(|f| !f.is_option())
+        .map(|f| f.raw_name.clone())
+        .collect::<Vec<_>>();
+
+    let mut schema = Map::new();
+    schema.insert("title".to_string(), json!(name));
+    schema.insert("type".to_string(), json!("object"));
+    schema.insert("properties".to_string(), Value::Object(properties));
+    schema.insert("required".to_string(), json!(required));
+    schema
+}
+
+fn property(field: &Field) -> Map<String, Value> {
+    let mut property = match parser(field) {
+        Some(parser) => for_parser(parser),
+        None => Map::from_iter([("type".to_string(), json!("null"))]),
+    };
+
+    // Overridden types hint at formats inference can't tell apart from strings.
+    let format = declared_type(field).and_then(|t| match last_segment(&t) {
+        "NaiveDate" => Some("date"),
+        "NaiveTime" => Some("time"),
+        "NaiveDateTime" | "DateTime" => Some("date-time"),
+        "Uuid" => Some("uuid"),
+        _ => None,
+    });
+    if let Some(format) = format {
+        property.insert("type".to_string(), json!("string"));
+        property.insert("format".to_string(), json!(format));
+    }
+
+    if field.is_option() && parser(field).is_some() {
+        let kind = property["type"].clone();
+        property.insert("type".to_string(), json!([kind, "null"]));
+    }
+
+    property
+}
+
+fn for_parser(parser: TypeParser) -> Map<String, Value> {
+    use TypeParser::*;
+
+    let property = match parser {
+        U8 => json!({ "type": "integer", "minimum": 0, "maximum": u8::MAX }),
+        U16 => json!({ "type": "integer", "minimum": 0, "maximum": u16::MAX }),
+        U32 => json!({ "type": "integer", "minimum": 0, "maximum": u32::MAX }),
+        U64 | U128 => json!({ "type": "integer", "minimum": 0 }),
+        I8 => json!({ "type": "integer", "minimum": i8::MIN, "maximum": i8::MAX }),
+        I16 => json!({ "type": "integer", "minimum": i16::MIN, "maximum": i16::MAX }),
+        I32 => json!({ "type": "integer", "minimum": i32::MIN, "maximum": i32::MAX }),
+        I64 | I128 => json!({ "type": "integer" }),
+        F32 | F64 => json!({ "type": "number" }),
+        Map => json!({ "type": "object", "additionalProperties": { "type": "string" } }),
+        Char => json!({ "type": "string", "minLength": 1, "maxLength": 1 }),
+        Duration | Path | String => json!({ "type": "string" }),
+    };
+
+    let Value::Object(property) = property else {
+        unreachable!("Properties are objects.");
+    };
+    property
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use crate::{emit::tests::sample, Emit};
+
+    #[test]
+    fn declared_types() {
+        assert_eq!(
+            sample(Emit::JsonSchema),
+            indoc! {r#"
+                {
+                  "$schema": "https://json-schema.org/draft/2020-12/schema",
+                  "title": "Order",
+                  "type": "object",
+                  "properties": {
+                    "sku": {
+                      "type": "string"
+                    },
+                    "total": {
+                      "type": "number"
+                    },
+                    "created": {
+                      "type": "string",
+                      "format": "date"
+                    },
+                    "secret": {
+                      "type": "string"
+                    },
+                    "note": {
+                      "type": [
+                        "string",
+                        "null"
+                      ],
+                      "minLength": 1,
+                      "maxLength": 1
+                    }
+                  },
+                  "required": [
+                    "sku",
+                    "total",
+                    "created",
+                    "secret"
+                  ]
+                }
+            "#}
+        );
+    }
+}
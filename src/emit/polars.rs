@@ -0,0 +1,136 @@
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use super::{declared_type, last_segment, parser};
+use crate::{field::Field, type_parser::TypeParser, Config, Error};
+
+/// A function building a Polars schema for the table, and one scanning the
+/// CSV file lazily with those dtypes instead of inferring its own.
+pub fn generate(config: &Config, name: &str, fields: &[Field]) -> Result<String, Error> {
+    let snake_name = name.to_case(Case::Snake);
+    let schema_fn = format_ident!("{snake_name}_schema");
+    let scan_fn = format_ident!("scan_{snake_name}");
+
+    let fields = fields
+        .iter()
+        .map(|f| {
+            let name = &f.raw_name;
+            let dtype = dtype(f);
+            quote! {
+                Field::new(#name.into(), #dtype)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let time_unit = fields
+        .iter()
+        .any(|f| f.to_string().contains("TimeUnit"))
+        .then(|| quote! {, TimeUnit});
+
+    let separator = format!("b'{}'", config.delimiter.escape_ascii())
+        .parse::<TokenStream>()
+        .unwrap();
+    let has_header = config.has_headers;
+
+    let items = [
+        quote! {
+            pub fn #schema_fn() -> polars::prelude::Schema {
+                use polars::prelude::{DataType, Field, Schema #time_unit};
+
+                Schema::from_iter([#(#fields),*])
+            }
+        },
+        quote! {
+            pub fn #scan_fn(
+                path: impl AsRef<std::path::Path>,
+            ) -> polars::prelude::PolarsResult<polars::prelude::LazyFrame> {
+                use polars::prelude::{LazyCsvReader, LazyFileListReader};
+
+                LazyCsvReader::new(path)
+                    .with_has_header(#has_header)
+                    .with_separator(#separator)
+                    .with_dtype_overwrite(Some(std::sync::Arc::new(#schema_fn())))
+                    .finish()
+            }
+        },
+    ];
+
+    let items = items
+        .into_iter()
+        .map(|item| {
+            let syntax_tree = syn::parse2(item).map_err(Error::CantGenerateCode)?;
+            Ok(prettyplease::unparse(&syntax_tree))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(items.join("\n"))
+}
+
+fn dtype(field: &Field) -> TokenStream {
+    use TypeParser::*;
+
+    match declared_type(field).as_deref().map(last_segment) {
+        Some("NaiveDate") => return quote! {DataType::Date},
+        Some("NaiveTime") => return quote! {DataType::Time},
+        Some("NaiveDateTime" | "DateTime") => {
+            return quote! {DataType::Datetime(TimeUnit::Microseconds, None)}
+        }
+        _ => {}
+    }
+
+    // Polars' CSV reader parses neither durations nor maps, so they stay strings.
+    let dtype = match parser(field) {
+        Some(U8) => quote! {UInt8},
+        Some(U16) => quote! {UInt16},
+        Some(U32) => quote! {UInt32},
+        Some(U64) => quote! {UInt64},
+        Some(I8) => quote! {Int8},
+        Some(I16) => quote! {Int16},
+        Some(I32) => quote! {Int32},
+        Some(I64) => quote! {Int64},
+        Some(I128) => quote! {Int128},
+        Some(F32) => quote! {Float32},
+        Some(F64) => quote! {Float64},
+        Some(U128 | Duration | Map | Char | Path | String) | None => quote! {String},
+    };
+
+    quote! {DataType::#dtype}
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use crate::{emit::tests::sample, Emit};
+
+    #[test]
+    fn declared_types() {
+        assert_eq!(
+            sample(Emit::Polars),
+            indoc! {r#"
+                pub fn order_schema() -> polars::prelude::Schema {
+                    use polars::prelude::{DataType, Field, Schema};
+                    Schema::from_iter([
+                        Field::new("sku".into(), DataType::String),
+                        Field::new("total".into(), DataType::Float32),
+                        Field::new("created".into(), DataType::Date),
+                        Field::new("secret".into(), DataType::String),
+                        Field::new("note".into(), DataType::String),
+                    ])
+                }
+
+                pub fn scan_order(
+                    path: impl AsRef<std::path::Path>,
+                ) -> polars::prelude::PolarsResult<polars::prelude::LazyFrame> {
+                    use polars::prelude::{LazyCsvReader, LazyFileListReader};
+                    LazyCsvReader::new(path)
+                        .with_has_header(true)
+                        .with_separator(b',')
+                        .with_dtype_overwrite(Some(std::sync::Arc::new(order_schema())))
+                        .finish()
+                }
+            "#}
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use super::{parser, plain_name};
+use crate::{field::Field, type_parser::TypeParser};
+
+/// A proto3 file with a message per table.
+pub fn generate(tables: &[(String, Vec<Field>)]) -> String {
+    let messages = tables
+        .iter()
+        .map(|(name, fields)| message(name, fields))
+        .collect::<Vec<_>>();
+
+    format!("syntax = \"proto3\";\n\n{}", messages.join("\n"))
+}
+
+fn message(name: &str, fields: &[Field]) -> String {
+    let mut code = format!("message {name} {{\n");
+
+    for (number, field) in (1..).zip(fields) {
+        let label = if field.is_option() { "optional " } else { "" };
+        code.push_str(&format!(
+            "  {label}{} {} = {number};\n",
+            field_type(parser(field)),
+            plain_name(field)
+        ));
+    }
+
+    code.push_str("}\n");
+    code
+}
+
+/// 128-bit integers and durations have no scalar type, and are kept as strings.
+fn field_type(parser: Option<TypeParser>) -> &'static str {
+    use TypeParser::*;
+
+    match parser {
+        Some(U8 | U16 | U32) => "uint32",
+        Some(U64) => "uint64",
+        Some(I8 | I16 | I32) => "int32",
+        Some(I64) => "int64",
+        Some(F32) => "float",
+        Some(F64) => "double",
+        Some(Map) => "map<string, string>",
+        Some(U128 | I128 | Duration | Char | Path | String) | None => "string",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use crate::{emit::tests::sample, Emit};
+
+    #[test]
+    fn declared_types() {
+        assert_eq!(
+            sample(Emit::Proto),
+            indoc! {r#"
+                syntax = "proto3";
+
+                message Order {
+                  string sku = 1;
+                  float total = 2;
+                  string created = 3;
+                  string secret = 4;
+                  optional string note = 5;
+                }
+            "#}
+        );
+    }
+}
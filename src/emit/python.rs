@@ -0,0 +1,239 @@
+use convert_case::{Case, Casing};
+
+use super::{parser, plain_name};
+use crate::{field::Field, type_parser::TypeParser};
+
+/// Names that can't be used as attributes, and get a trailing underscore.
+const KEYWORDS: [&str; 35] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// The flavor of Python class to generate.
+#[derive(Copy, Clone, PartialEq)]
+pub enum Kind {
+    Pydantic,
+    Dataclass,
+}
+
+/// A module with a class per table, and the imports they need.
+pub fn generate(kind: Kind, tables: &[(String, Vec<Field>)]) -> String {
+    let fields = || tables.iter().flat_map(|(_, fields)| fields);
+
+    let aliased = fields().any(|f| attribute_name(kind, f) != f.raw_name);
+
+    let mut imports = vec![];
+    if kind == Kind::Dataclass {
+        imports.push(if aliased {
+            "from dataclasses import dataclass, field"
+        } else {
+            "from dataclasses import dataclass"
+        });
+    }
+    if fields().any(|f| parser(f) == Some(TypeParser::Path)) {
+        imports.push("from pathlib import Path");
+    }
+    if fields().any(|f| f.is_option() && parser(f).is_some()) {
+        imports.push("from typing import Optional");
+    }
+    if kind == Kind::Pydantic {
+        imports.push(if aliased {
+            "from pydantic import BaseModel, Field"
+        } else {
+            "from pydantic import BaseModel"
+        });
+    }
+
+    let classes = tables
+        .iter()
+        .map(|(name, fields)| class(kind, name, fields))
+        .collect::<Vec<_>>();
+
+    format!("{}\n\n\n{}", imports.join("\n"), classes.join("\n\n"))
+}
+
+fn class(kind: Kind, name: &str, fields: &[Field]) -> String {
+    let mut code = match kind {
+        Kind::Pydantic => format!("class {name}(BaseModel):\n"),
+        Kind::Dataclass => format!("@dataclass\nclass {name}:\n"),
+    };
+
+    if fields.is_empty() {
+        code.push_str("    pass\n");
+    }
+
+    for field in fields {
+        let name = attribute_name(kind, field);
+        let annotation = match (parser(field), field.is_option()) {
+            (Some(parser), true) => format!("Optional[{}]", python_type(parser)),
+            (Some(parser), false) => python_type(parser).to_string(),
+            (None, _) => "None".to_string(),
+        };
+
+        // Dataclass fields without defaults can't follow ones with them, so only
+        // models get defaults. Renamed dataclass fields keep their column in
+        // metadata, as dataclasses have no aliases.
+        let default = match kind {
+            Kind::Pydantic if name != field.raw_name && field.is_option() => {
+                format!(
+                    " = Field(default=None, alias={})",
+                    string_literal(&field.raw_name)
+                )
+            }
+            Kind::Pydantic if name != field.raw_name => {
+                format!(" = Field(alias={})", string_literal(&field.raw_name))
+            }
+            Kind::Pydantic if field.is_option() => " = None".to_string(),
+            Kind::Dataclass if name != field.raw_name => {
+                format!(
+                    " = field(metadata={{\"column\": {}}})",
+                    string_literal(&field.raw_name)
+                )
+            }
+            _ => String::new(),
+        };
+
+        code.push_str(&format!("    {name}: {annotation}{default}\n"));
+    }
+
+    code
+}
+
+/// A double-quoted Python string, escaping what it can't hold as is.
+fn string_literal(s: &str) -> String {
+    let mut literal = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' | '\\' => {
+                literal.push('\\');
+                literal.push(c);
+            }
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c if c.is_control() => literal.push_str(&format!("\\x{:02x}", c as u32)),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// An attribute named `field` would hide `dataclasses.field` from the rest of
+/// a dataclass's body.
+fn attribute_name(kind: Kind, field: &Field) -> String {
+    let name = plain_name(field).to_case(Case::Snake);
+
+    if KEYWORDS.contains(&name.as_str()) || (kind == Kind::Dataclass && name == "field") {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+/// Cells holding durations and maps are kept as the strings they are.
+fn python_type(parser: TypeParser) -> &'static str {
+    use TypeParser::*;
+
+    match parser {
+        U8 | U16 | U32 | U64 | U128 | I8 | I16 | I32 | I64 | I128 => "int",
+        F32 | F64 => "float",
+        Path => "Path",
+        Duration | Map | Char | String => "str",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use crate::{emit::tests::sample, run_on_str, Config, Emit};
+
+    #[test]
+    fn model() {
+        let config = Config::builder()
+            .emit(Emit::Pydantic)
+            .struct_name("Order")
+            .build();
+        let csv = "Order ID,class,caf\u{e9} \"x\",a\u{80}b\n1,a,,b\n";
+
+        assert_eq!(
+            run_on_str(csv, &config).unwrap(),
+            indoc! {r#"
+                from pydantic import BaseModel, Field
+
+
+                class Order(BaseModel):
+                    order_id: int = Field(alias="Order ID")
+                    class_: str = Field(alias="class")
+                    cafe_x: None = Field(default=None, alias="café \"x\"")
+                    ab: str = Field(alias="a\x80b")
+            "#}
+        );
+    }
+
+    #[test]
+    fn dataclass() {
+        let config = Config::builder()
+            .emit(Emit::Dataclass)
+            .struct_name("Order")
+            .build();
+        let csv = "Order ID,class,field,note\n1,a,b,c\n";
+
+        assert_eq!(
+            run_on_str(csv, &config).unwrap(),
+            indoc! {r#"
+                from dataclasses import dataclass, field
+
+
+                @dataclass
+                class Order:
+                    order_id: int = field(metadata={"column": "Order ID"})
+                    class_: str = field(metadata={"column": "class"})
+                    field_: str = field(metadata={"column": "field"})
+                    note: str
+            "#}
+        );
+    }
+
+    #[test]
+    fn declared_types_pydantic() {
+        assert_eq!(
+            sample(Emit::Pydantic),
+            indoc! {r"
+                from typing import Optional
+                from pydantic import BaseModel
+
+
+                class Order(BaseModel):
+                    sku: str
+                    total: float
+                    created: str
+                    secret: str
+                    note: Optional[str] = None
+            "}
+        );
+    }
+
+    #[test]
+    fn declared_types_dataclass() {
+        assert_eq!(
+            sample(Emit::Dataclass),
+            indoc! {r"
+                from dataclasses import dataclass
+                from typing import Optional
+
+
+                @dataclass
+                class Order:
+                    sku: str
+                    total: float
+                    created: str
+                    secret: str
+                    note: Optional[str]
+            "}
+        );
+    }
+}
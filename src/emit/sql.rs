@@ -0,0 +1,106 @@
+use convert_case::{Case, Casing};
+
+use super::{parser, plain_name};
+use crate::{field::Field, type_parser::TypeParser, SqlDialect};
+
+/// A `CREATE TABLE` statement, with a column per field.
+pub fn generate(dialect: SqlDialect, name: &str, fields: &[Field]) -> String {
+    let columns = fields
+        .iter()
+        .map(|f| {
+            let null = if f.is_option() { "" } else { " NOT NULL" };
+            format!(
+                "    {} {}{null}",
+                quote(dialect, plain_name(f)),
+                column_type(dialect, parser(f))
+            )
+        })
+        .collect::<Vec<_>>();
+
+    format!(
+        "CREATE TABLE {} (\n{}\n);\n",
+        quote(dialect, &name.to_case(Case::Snake)),
+        columns.join(",\n")
+    )
+}
+
+pub fn quote(dialect: SqlDialect, identifier: &str) -> String {
+    match dialect {
+        SqlDialect::Mysql => format!("`{}`", identifier.replace('`', "``")),
+        SqlDialect::Postgres | SqlDialect::Sqlite => {
+            format!("\"{}\"", identifier.replace('"', "\"\""))
+        }
+    }
+}
+
+/// The column type able to hold every value of the inferred type. Columns
+/// without any values are text.
+fn column_type(dialect: SqlDialect, parser: Option<TypeParser>) -> &'static str {
+    use TypeParser::*;
+
+    let Some(parser) = parser else {
+        return "TEXT";
+    };
+
+    match dialect {
+        SqlDialect::Postgres => match parser {
+            U8 | I8 | I16 => "SMALLINT",
+            U16 | I32 => "INTEGER",
+            U32 | I64 => "BIGINT",
+            U64 | U128 | I128 => "NUMERIC",
+            F32 => "REAL",
+            F64 => "DOUBLE PRECISION",
+            Duration => "INTERVAL",
+            Map => "JSONB",
+            Char => "CHAR(1)",
+            Path | String => "TEXT",
+        },
+        SqlDialect::Mysql => match parser {
+            U8 => "TINYINT UNSIGNED",
+            I8 => "TINYINT",
+            U16 => "SMALLINT UNSIGNED",
+            I16 => "SMALLINT",
+            U32 => "INT UNSIGNED",
+            I32 => "INT",
+            U64 => "BIGINT UNSIGNED",
+            I64 => "BIGINT",
+            U128 | I128 => "DECIMAL(39, 0)",
+            F32 => "FLOAT",
+            F64 => "DOUBLE",
+            Duration => "TIME",
+            Map => "JSON",
+            Char => "CHAR(1)",
+            Path | String => "TEXT",
+        },
+        // SQLite integers are signed 64-bit, anything larger is stored as a number.
+        SqlDialect::Sqlite => match parser {
+            U8 | U16 | U32 | I8 | I16 | I32 | I64 => "INTEGER",
+            U64 | U128 | I128 => "NUMERIC",
+            F32 | F64 => "REAL",
+            Duration | Map | Char | Path | String => "TEXT",
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use crate::{emit::tests::sample, Emit};
+
+    #[test]
+    fn declared_types() {
+        assert_eq!(
+            sample(Emit::Sql),
+            indoc! {r#"
+                CREATE TABLE "order" (
+                    "sku" TEXT NOT NULL,
+                    "total" REAL NOT NULL,
+                    "created" TEXT NOT NULL,
+                    "secret" TEXT NOT NULL,
+                    "note" CHAR(1)
+                );
+            "#}
+        );
+    }
+}
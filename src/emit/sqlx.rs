@@ -0,0 +1,108 @@
+use convert_case::{Case, Casing};
+use quote::{format_ident, quote};
+
+use super::{parser, plain_name, sql, sql_rust_type};
+use crate::{field::Field, Config, Error, SqlDialect};
+
+/// A struct deriving `sqlx::FromRow`, and optionally an `INSERT` statement
+/// with a placeholder per column.
+pub fn generate(config: &Config, name: &str, fields: &[Field]) -> Result<String, Error> {
+    let struct_name = format_ident!("{}", name);
+
+    let struct_fields = fields.iter().map(|f| {
+        let field_name = format_ident!("{}", &f.name);
+
+        let rust_type = sql_rust_type(parser(f));
+        let rust_type = if f.is_option() {
+            format!("Option<{rust_type}>")
+        } else {
+            rust_type.to_string()
+        };
+        let rust_type = syn::Type::Verbatim(rust_type.parse().unwrap());
+
+        let maybe_rename = if f.name.starts_with("r#") {
+            let column = plain_name(f);
+            quote! {#[sqlx(rename = #column)]}
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            #maybe_rename
+            pub #field_name: #rust_type,
+        }
+    });
+
+    let model = quote! {
+        #[derive(Debug, sqlx::FromRow)]
+        pub struct #struct_name {
+            #(#struct_fields)*
+        }
+    };
+
+    let syntax_tree = syn::parse2(model).map_err(Error::CantGenerateCode)?;
+    let mut code = prettyplease::unparse(&syntax_tree);
+
+    if config.insert_template {
+        let dialect = config.sql_dialect;
+        let table_name = name.to_case(Case::Snake);
+        let columns = fields
+            .iter()
+            .map(|f| sql::quote(dialect, plain_name(f)))
+            .collect::<Vec<_>>();
+        let placeholders = (1..=fields.len())
+            .map(|i| match dialect {
+                SqlDialect::Postgres => format!("${i}"),
+                SqlDialect::Mysql | SqlDialect::Sqlite => "?".to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        let statement = format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            sql::quote(dialect, &table_name),
+            columns.join(", "),
+            placeholders.join(", ")
+        );
+        let const_name = format_ident!("INSERT_{}", table_name.to_ascii_uppercase());
+
+        // A raw string, so that quoted identifiers stay readable.
+        let statement = if statement.contains("\"#") {
+            quote! {#statement}
+        } else {
+            format!("r#\"{statement}\"#").parse().unwrap()
+        };
+
+        let insert = quote! {
+            pub const #const_name: &str = #statement;
+        };
+        let syntax_tree = syn::parse2(insert).map_err(Error::CantGenerateCode)?;
+        code.push('\n');
+        code.push_str(&prettyplease::unparse(&syntax_tree));
+    }
+
+    Ok(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use crate::{emit::tests::sample, Emit};
+
+    #[test]
+    fn declared_types() {
+        assert_eq!(
+            sample(Emit::Sqlx),
+            indoc! {r"
+                #[derive(Debug, sqlx::FromRow)]
+                pub struct Order {
+                    pub sku: String,
+                    pub total: f32,
+                    pub created: String,
+                    pub secret: String,
+                    pub note: Option<String>,
+                }
+            "}
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use super::parser;
+use crate::{field::Field, type_parser::TypeParser};
+
+/// An exported `interface`, with a property per column named as in the header.
+pub fn generate(name: &str, fields: &[Field]) -> String {
+    let mut code = format!("export interface {name} {{\n");
+
+    for field in fields {
+        let optional = if field.is_option() { "?" } else { "" };
+        code.push_str(&format!(
+            "  {}{optional}: {};\n",
+            property_name(&field.raw_name),
+            property_type(parser(field))
+        ));
+    }
+
+    code.push_str("}\n");
+    code
+}
+
+/// The header as is when it's a valid identifier, quoted otherwise.
+fn property_name(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+
+    if is_identifier {
+        name.to_string()
+    } else {
+        string_literal(name)
+    }
+}
+
+/// A double-quoted JavaScript string, escaping what it can't hold as is.
+fn string_literal(s: &str) -> String {
+    let mut literal = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' | '\\' => {
+                literal.push('\\');
+                literal.push(c);
+            }
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c if c.is_control() || c == '\u{2028}' || c == '\u{2029}' => {
+                literal.push_str(&format!("\\u{:04x}", c as u32));
+            }
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// Integers that may not fit a `number` exactly are kept as strings.
+fn property_type(parser: Option<TypeParser>) -> &'static str {
+    use TypeParser::*;
+
+    match parser {
+        Some(U8 | U16 | U32 | I8 | I16 | I32 | F32 | F64) => "number",
+        Some(Map) => "Record<string, string>",
+        Some(U64 | U128 | I64 | I128 | Duration | Char | Path | String) => "string",
+        None => "null",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use crate::{emit::tests::sample, run_on_str, Config, Emit};
+
+    #[test]
+    fn interface() {
+        let config = Config::builder()
+            .emit(Emit::Typescript)
+            .struct_name("Order")
+            .build();
+        let csv = "id,big,say \"hi\",a\u{7}b,note\n1,18446744073709551615,a,b,\n";
+
+        assert_eq!(
+            run_on_str(csv, &config).unwrap(),
+            indoc! {r#"
+                export interface Order {
+                  id: number;
+                  big: string;
+                  "say \"hi\"": string;
+                  "a\u0007b": string;
+                  note?: null;
+                }
+            "#}
+        );
+    }
+
+    #[test]
+    fn declared_types() {
+        assert_eq!(
+            sample(Emit::Typescript),
+            indoc! {r"
+                export interface Order {
+                  sku: string;
+                  total: number;
+                  created: string;
+                  secret: string;
+                  note?: string;
+                }
+            "}
+        );
+    }
+}
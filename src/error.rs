@@ -1,11 +1,125 @@
+use std::{fmt, io, path::PathBuf};
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
+    #[error("Could not read '{}': {source}", .path.display())]
+    CantReadFile {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("Could not write '{}': {source}", .path.display())]
+    CantWriteFile {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("Could not start worker threads: {0}")]
+    CantStartThreads(#[source] rayon::ThreadPoolBuildError),
+
     #[error("Could not parse headers: {0}")]
     CantParseFieldHeaders(#[source] csv::Error),
 
-    #[error("Could not parse record: {0}")]
-    CantParseRecord(#[source] csv::Error),
+    #[error("Could not use provided headers: expected {expected} names, found {found}")]
+    HeaderCountMismatch { expected: usize, found: usize },
+
+    #[error("Could not merge inputs: expected headers {expected:?}, found {found:?}")]
+    MismatchedHeaders {
+        expected: Vec<String>,
+        found: Vec<String>,
+    },
+
+    #[error("Could not filter rows: no column named '{0}'")]
+    UnknownFilterColumn(String),
+
+    #[error("Could not split rows: no column named '{0}'")]
+    UnknownSplitColumn(String),
+
+    #[error("Could not generate code: no column named '{0}' for the primary key")]
+    UnknownPrimaryKey(String),
+
+    #[error("Could not generate code: '{0}' has no column named 'id' or with distinct values, to be its primary key")]
+    NoPrimaryKey(String),
+
+    #[error("Could not parse record on {context}: {source}")]
+    CantParseRecord {
+        context: Box<RecordContext>,
+        #[source]
+        source: csv::Error,
+    },
+
+    #[error("Could not parse record on {context}: expected {expected} fields, as in the header row, found {}", .context.fields.len())]
+    TooManyFields {
+        context: Box<RecordContext>,
+        expected: usize,
+    },
+
+    #[error("Could not parse record on {context}: invalid UTF-8")]
+    InvalidUtf8 {
+        context: Box<RecordContext>,
+        #[source]
+        source: std::str::Utf8Error,
+    },
+
+    #[error("Could not parse schema: {0}")]
+    CantParseSchema(#[source] serde_json::Error),
+
+    #[error("Could not generate code: '{name}' from '{header}' is not a valid identifier")]
+    InvalidIdentifier { header: String, name: String },
+
+    #[error("Could not generate code: '{type_name}' for column '{header}' is not a valid type")]
+    InvalidType { header: String, type_name: String },
 
     #[error("Could not generate code: {0}")]
     CantGenerateCode(#[source] syn::Error),
 }
+
+impl Error {
+    /// Where in the input the error is, for errors about a record.
+    pub fn record_context(&self) -> Option<&RecordContext> {
+        match self {
+            Error::CantParseRecord { context, .. }
+            | Error::InvalidUtf8 { context, .. }
+            | Error::TooManyFields { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+}
+
+/// The record an error is about, to find it in a large input.
+#[derive(Clone, Debug)]
+pub struct RecordContext {
+    /// Line the record starts on, counting from 1.
+    pub line: u64,
+    /// Offset of the record from the start of the input.
+    pub byte: u64,
+    /// Index and header of the offending column, where there is one.
+    pub column: Option<(usize, String)>,
+    /// Fields of the record, with invalid UTF-8 replaced.
+    pub fields: Vec<String>,
+}
+
+impl RecordContext {
+    /// The offending value, where there is one.
+    pub fn value(&self) -> Option<&str> {
+        let (index, _) = self.column.as_ref()?;
+        self.fields.get(*index).map(String::as_str)
+    }
+}
+
+impl fmt::Display for RecordContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {} (byte {})", self.line, self.byte)?;
+
+        if let Some((_, header)) = &self.column {
+            write!(f, ", column '{header}'")?;
+        }
+        if let Some(value) = self.value() {
+            write!(f, ", value '{value}'")?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{field::Field, type_parser::TypeParser, Config, Table};
+
+/// Generates `rows` records of plausible values for the columns of `table`,
+/// as CSV in the input's dialect, headers first.
+///
+/// Numbers are drawn from the range seen, and columns holding a few repeated
+/// values from those values. Other columns get random text of the lengths
+/// seen, so free-form values, which may identify people, never make it into
+/// the output. Statistics have to be collected for all of that, as with
+/// [`Config::stats_docs`]; columns without them get values of their type.
+/// The same `seed` gives the same records.
+pub fn fake(table: &Table, rows: usize, seed: u64, config: &Config) -> String {
+    let fields = table.fields();
+    let mut random = Random(seed);
+
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(config.delimiter)
+        .quote(config.quote)
+        .from_writer(vec![]);
+
+    if config.has_headers {
+        writer
+            .write_record(fields.iter().map(|f| &f.raw_name))
+            .expect("Writing to a Vec can't fail.");
+    }
+
+    for _ in 0..rows {
+        let record = fields
+            .iter()
+            .map(|f| value(f, &mut random))
+            .collect::<Vec<_>>();
+        writer
+            .write_record(&record)
+            .expect("Writing to a Vec can't fail.");
+    }
+
+    let csv = writer.into_inner().expect("Writing to a Vec can't fail.");
+    String::from_utf8(csv).expect("Records are valid UTF-8.")
+}
+
+/// A random value for the field, or an empty cell as often as they were seen.
+fn value(field: &Field, random: &mut Random) -> String {
+    let Some(parser) = field.type_parser() else {
+        return String::new();
+    };
+
+    if let Some(stats) = &field.stats {
+        if random.below(100) < stats.null_percentage().round() as u64 {
+            return String::new();
+        }
+        if let Some(categories) = stats.categories().filter(|_| !field.is_redacted()) {
+            let index = random.below(categories.len() as u64) as usize;
+            return categories.iter().nth(index).cloned().unwrap_or_default();
+        }
+    }
+
+    let stats = field.stats.as_ref();
+    let (min, max) = match stats.and_then(|s| s.min.zip(s.max)) {
+        Some((min, max)) if min.is_finite() && max.is_finite() => (min, max),
+        _ => (0.0, 100.0),
+    };
+    let max_len = stats.and_then(|s| s.max_len).unwrap_or(8).max(1);
+
+    match parser {
+        TypeParser::F32 | TypeParser::F64 => {
+            format!("{:.2}", min + random.fraction() * (max - min))
+        }
+        parser if parser.is_numeric() => {
+            let (min, max) = (min.ceil() as i128, max.floor() as i128);
+            let span = (max - min).max(0) as u64;
+            (min + random.below(span.saturating_add(1)) as i128).to_string()
+        }
+        TypeParser::Duration => format!("{}s", random.below(3600)),
+        TypeParser::Map => format!("{}={}", random.word(1, 8), random.below(100)),
+        TypeParser::Char => random.word(1, 1),
+        TypeParser::Path => format!("data/{}.csv", random.word(1, 8)),
+        _ => random.word(1, max_len),
+    }
+}
+
+/// SplitMix64, good enough for fixtures, and reproducible across platforms.
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `0` up to, but excluding, `n`.
+    fn below(&mut self, n: u64) -> u64 {
+        if n == 0 {
+            return 0;
+        }
+        self.next() % n
+    }
+
+    /// A number from `0` up to, but excluding, `1`.
+    fn fraction(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Lowercase letters, between `min` and `max` of them.
+    fn word(&mut self, min: usize, max: usize) -> String {
+        let len = min + self.below((max - min + 1) as u64) as usize;
+        (0..len)
+            .map(|_| (b'a' + self.below(26) as u8) as char)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fake;
+    use crate::{infer, Config};
+
+    #[test]
+    fn plausible_values() {
+        let config = Config::builder().stats_docs(true).build();
+        let csv = "id,status,name,score\n3,open,Alice,1.5\n9,done,Bob,\n5,open,Carol,2\n";
+        let reader = config.reader_builder().from_reader(csv.as_bytes());
+        let schema = infer(reader, &config).unwrap();
+
+        let output = fake(&schema.tables[0], 50, 7, &config);
+        assert_eq!(output, fake(&schema.tables[0], 50, 7, &config));
+
+        let mut reader = csv::Reader::from_reader(output.as_bytes());
+        assert_eq!(
+            reader.headers().unwrap(),
+            vec!["id", "status", "name", "score"]
+        );
+
+        let records = reader.records().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(records.len(), 50);
+
+        for record in records {
+            assert!((3..=9).contains(&record[0].parse::<u8>().unwrap()));
+            assert!(["open", "done"].contains(&&record[1]));
+            assert!(!["Alice", "Bob", "Carol"].contains(&&record[2]));
+            assert!((1..=5).contains(&record[2].len()));
+            if !record[3].is_empty() {
+                assert!((1.5..=2.0).contains(&record[3].parse::<f64>().unwrap()));
+            }
+        }
+    }
+}
@@ -1,58 +1,705 @@
+use std::collections::HashSet;
+
 use convert_case::{Case, Casing};
+use serde::{Deserialize, Serialize};
 
-use crate::{keywords, type_parser::TypeParser};
+use crate::{
+    keywords::{self, Keyword},
+    stats::{Stats, SAMPLES},
+    type_parser::{Shape, TypeParser},
+    Config, Emit, EmptyColumns, FieldCase, TypeRegistry,
+};
 
-#[derive(Clone, Debug)]
+/// A column, and what was inferred about its values.
+/// Only what was inferred is serialized, the rest comes from the config.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Field {
+    /// Identifier of the generated field.
+    #[serde(skip)]
     pub name: String,
+    /// Header of the column, as found in the input.
     pub raw_name: String,
     valid_parsers: Vec<TypeParser>,
-    optional: bool,
+    /// Indices of the custom types accepting all values seen.
+    #[serde(default)]
+    valid_custom_types: Vec<usize>,
+    /// Cells seen, and how many of them were empty.
+    cells: usize,
+    empty_cells: usize,
     is_empty: bool,
+    pub stats: Option<Stats>,
+    /// Types ruled out, in order, with the first value that didn't parse as them.
+    #[serde(default)]
+    eliminated: Vec<Elimination>,
+    /// The first few distinct values seen.
+    #[serde(default)]
+    samples: Vec<String>,
+    #[serde(skip)]
+    type_override: Option<String>,
+    #[serde(skip)]
+    optional_override: Option<bool>,
+    /// Fraction of empty cells above which the field is optional.
+    #[serde(skip)]
+    optional_threshold: f64,
+    #[serde(skip)]
+    empty_columns: EmptyColumns,
+    #[serde(skip)]
+    borrow: bool,
+    #[serde(skip)]
+    flatten: bool,
+    /// Whether the field belongs to a struct flattened into its parent.
+    #[serde(skip)]
+    nested: bool,
+    /// Whether the field captures a column beyond the header row.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    extra: bool,
+    #[serde(skip)]
+    naming: Naming,
+    #[serde(skip)]
+    custom_types: TypeRegistry,
+    #[serde(skip)]
+    null_values: Vec<String>,
+    /// Whether values are masked as they are deserialized.
+    #[serde(skip)]
+    redact: bool,
+}
+
+/// A type ruled out for a column, and the cell that ruled it out.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Elimination {
+    pub parser: TypeParser,
+    /// Line of the record holding the cell, or 0 where it isn't known.
+    pub line: u64,
+    pub value: String,
 }
 
 impl Field {
-    pub fn update_for(&mut self, field: &str) {
-        if field.is_empty() {
-            self.optional = true;
+    /// Narrows the field's type for the given value. Returns whether anything changed.
+    pub fn update_for(&mut self, field: &str) -> bool {
+        self.update_at(field, 0)
+    }
+
+    /// Like [`Field::update_at`], for a cell straight from a byte record,
+    /// trimmed with `trim`. Numbers every remaining type still takes are
+    /// counted from their bytes alone; other cells are checked to be UTF-8,
+    /// and trimmed of Unicode whitespace too.
+    pub(crate) fn update_bytes(
+        &mut self,
+        cell: &[u8],
+        trim: bool,
+        line: u64,
+    ) -> Result<bool, std::str::Utf8Error> {
+        let bytes = if trim { cell.trim_ascii() } else { cell };
+        if self.counts_as_number(bytes) {
+            let was_optional = self.has_empty_cells();
+            self.cells += 1;
+            return Ok(self.optionality_changed(was_optional, line));
+        }
+
+        let text = std::str::from_utf8(cell)?;
+        Ok(self.update_at(if trim { text.trim() } else { text }, line))
+    }
+
+    /// Whether a cell would leave the field as it is, but for its count of
+    /// cells, without looking at it as text.
+    fn counts_as_number(&self, bytes: &[u8]) -> bool {
+        // Statistics, samples and custom types all need the text.
+        if self.is_empty || self.stats.is_some() || !self.valid_custom_types.is_empty() {
+            return false;
+        }
+        if self.samples.len() < SAMPLES && !self.samples.iter().any(|s| s.as_bytes() == bytes) {
+            return false;
+        }
+        if self.null_values.iter().any(|n| n.as_bytes() == bytes) {
+            return false;
+        }
+
+        let shape = Shape::of_bytes(bytes);
+        shape != Shape::Other
+            && self.valid_parsers.iter().all(|p| match p {
+                // Numbers are ASCII, a byte per character.
+                TypeParser::Char => bytes.len() == 1,
+                p => p.accepts_shape(shape) == Some(true),
+            })
+    }
+
+    /// Like [`Field::update_for`], noting the line the value is on for reports.
+    pub(crate) fn update_at(&mut self, field: &str, line: u64) -> bool {
+        let field = match self.null_values.iter().any(|n| n == field) {
+            true => "",
+            false => field,
+        };
+        if let Some(stats) = self.stats.as_mut() {
+            stats.update_for(field);
+        }
+
+        let was_optional = self.has_empty_cells();
+        self.cells += 1;
+
+        let narrowed = if field.is_empty() {
+            self.empty_cells += 1;
+            false
         } else {
-            self.valid_parsers.retain(|parser| parser.can_parse(field));
-            self.is_empty = false;
+            if self.samples.len() < SAMPLES && !self.samples.iter().any(|s| s == field) {
+                self.samples.push(field.to_string());
+            }
+
+            let parsers = self.valid_parsers.len();
+            let shape = Shape::of(field);
+            let eliminated_before = self.eliminated.len();
+            let eliminated = &mut self.eliminated;
+            self.valid_parsers.retain(|&parser| {
+                let accepted = parser.accepts(shape, field);
+                if !accepted {
+                    eliminated.push(Elimination {
+                        parser,
+                        line,
+                        value: field.to_string(),
+                    });
+                }
+                accepted
+            });
+            if log::log_enabled!(log::Level::Debug) && eliminated_before != self.eliminated.len() {
+                let types = self.eliminated[eliminated_before..]
+                    .iter()
+                    .map(|e| e.parser.type_name(false))
+                    .collect::<Vec<_>>();
+                log::debug!(
+                    "column {:?}: not {}, because of {field:?} on line {line}",
+                    self.raw_name,
+                    types.join(", ")
+                );
+            }
+            let custom_types = self.valid_custom_types.len();
+            self.custom_types
+                .retain_parsing(&mut self.valid_custom_types, field);
+
+            std::mem::replace(&mut self.is_empty, false)
+                || parsers != self.valid_parsers.len()
+                || custom_types != self.valid_custom_types.len()
+        };
+
+        narrowed | self.optionality_changed(was_optional, line)
+    }
+
+    /// Whether the field became optional, or stopped being so, with the last cell.
+    fn optionality_changed(&self, was_optional: bool, line: u64) -> bool {
+        let is_optional = self.has_empty_cells();
+        if was_optional != is_optional {
+            log::debug!(
+                "column {:?}: {} optional on line {line}, with {} of {} cells empty",
+                self.raw_name,
+                if is_optional { "became" } else { "no longer" },
+                self.empty_cells,
+                self.cells
+            );
         }
+
+        was_optional != is_optional
     }
 
-    pub fn type_name(&self) -> &'static str {
+    /// The parser chosen for this field, or `None` if no values were seen.
+    pub fn type_parser(&self) -> Option<TypeParser> {
         if self.is_empty {
-            return "Option<()>";
+            return None;
         }
 
-        TypeParser::all()
+        let parser = TypeParser::all()
             .into_iter()
             .find(|p| self.valid_parsers.contains(p))
-            .unwrap_or(TypeParser::String)
-            .type_name(self.optional)
+            .unwrap_or(TypeParser::String);
+
+        Some(parser)
     }
-}
 
-impl From<&str> for Field {
-    fn from(field: &str) -> Self {
-        // Handle punctuation, and convert to snake_case.
-        let mut name = field
-            .replace(|c: char| c.is_ascii_punctuation(), "_")
-            .trim_start_matches('_')
-            .to_case(Case::Snake);
+    /// The parser chosen for this field, unless its type is overridden or a
+    /// custom one.
+    pub fn builtin_parser(&self) -> Option<TypeParser> {
+        if self.redact || self.type_override.is_some() || self.custom_type().is_some() {
+            return None;
+        }
+
+        self.type_parser()
+    }
 
-        // Check for reserved words.
-        if keywords::check(&name) {
-            name = format!("r#{}", name);
+    /// Name of the generated helper needed to deserialize this field, if any.
+    pub fn deserialize_with(&self) -> Option<&'static str> {
+        if self.redact {
+            return match self.is_optional_or_empty() {
+                true => Some("redact_optional_string"),
+                false => Some("redact_string"),
+            };
+        }
+
+        let parser = self.builtin_parser()?;
+        match parser.deserialize_with(self.is_optional()) {
+            Some(helper) => Some(helper),
+            // Null values don't parse as the type, but should still be `None`.
+            None if self.is_optional()
+                && !self.null_values.is_empty()
+                && parser != TypeParser::String =>
+            {
+                Some("csv::invalid_option")
+            }
+            // Flattened structs are read from values serde has already typed,
+            // so an empty cell is some empty text rather than `None`.
+            None if self.nested && self.is_optional() && parser != TypeParser::String => {
+                Some("deserialize_flattened_option")
+            }
+            None => None,
+        }
+    }
+
+    /// Name of the generated helper needed to serialize this field, if any.
+    pub fn serialize_with(&self) -> Option<&'static str> {
+        self.builtin_parser()?.serialize_with(self.is_optional())
+    }
+
+    /// Combines inference results for the same column from another dataset.
+    pub fn merge(&mut self, other: &Field) {
+        self.valid_parsers
+            .retain(|p| other.valid_parsers.contains(p));
+        self.valid_custom_types
+            .retain(|i| other.valid_custom_types.contains(i));
+        self.cells += other.cells;
+        self.empty_cells += other.empty_cells;
+        self.is_empty &= other.is_empty;
+
+        // Keeps the earliest elimination of each type, as reading in order would.
+        for elimination in &other.eliminated {
+            match self
+                .eliminated
+                .iter_mut()
+                .find(|e| e.parser == elimination.parser)
+            {
+                Some(e) if e.line > elimination.line => *e = elimination.clone(),
+                Some(_) => {}
+                None => self.eliminated.push(elimination.clone()),
+            }
+        }
+        self.eliminated.sort_by_key(|e| e.line);
+        for sample in &other.samples {
+            if self.samples.len() < SAMPLES && !self.samples.contains(sample) {
+                self.samples.push(sample.clone());
+            }
         }
 
-        Field {
+        if let (Some(stats), Some(other)) = (self.stats.as_mut(), other.stats.as_ref()) {
+            stats.merge(other);
+        }
+    }
+
+    /// Whether the field is named in the given list, by raw or sanitized name.
+    pub fn is_listed(&self, columns: &[String]) -> bool {
+        columns
+            .iter()
+            .any(|c| *c == self.raw_name || *c == self.name)
+    }
+
+    /// The type given by the user, if any.
+    pub fn type_override(&self) -> Option<&str> {
+        self.type_override.as_deref()
+    }
+
+    /// Uses this type instead of the inferred one, as [`Config::type_overrides`] does.
+    pub fn set_type_override(&mut self, type_name: Option<String>) {
+        self.type_override = type_name;
+    }
+
+    /// Makes the field optional or not whatever was seen, or leaves it to the
+    /// values seen with `None`.
+    pub fn set_optional_override(&mut self, optional: Option<bool>) {
+        self.optional_override = optional;
+    }
+
+    /// Types ruled out, with the first value that didn't parse as them.
+    pub fn eliminated(&self) -> &[Elimination] {
+        &self.eliminated
+    }
+
+    /// The first few distinct values seen.
+    pub fn samples(&self) -> &[String] {
+        &self.samples
+    }
+
+    /// How many cells were seen.
+    pub fn cells(&self) -> usize {
+        self.cells
+    }
+
+    /// How many of the cells seen were empty.
+    pub fn empty_cells(&self) -> usize {
+        self.empty_cells
+    }
+
+    /// Whether no values were seen for this field.
+    pub fn is_empty(&self) -> bool {
+        self.is_empty
+    }
+
+    /// Whether the field should be wrapped in `Option`, honoring user overrides.
+    fn is_optional(&self) -> bool {
+        self.optional_override.unwrap_or(self.has_empty_cells())
+    }
+
+    /// Whether a field of a type other than the inferred one is optional.
+    /// Columns with no values seen can only be.
+    fn is_optional_or_empty(&self) -> bool {
+        self.optional_override
+            .unwrap_or(self.has_empty_cells() || self.is_empty)
+    }
+
+    /// Whether values are masked as they are deserialized.
+    pub fn is_redacted(&self) -> bool {
+        self.redact
+    }
+
+    /// Whether more than the tolerated fraction of cells were empty.
+    fn has_empty_cells(&self) -> bool {
+        self.empty_fraction() > self.optional_threshold
+    }
+
+    /// Fraction of the cells seen that were empty.
+    pub fn empty_fraction(&self) -> f64 {
+        if self.cells == 0 {
+            0.0
+        } else {
+            self.empty_cells as f64 / self.cells as f64
+        }
+    }
+
+    /// Whether the generated type is an `Option`.
+    pub fn is_option(&self) -> bool {
+        self.type_name().starts_with("Option<")
+    }
+
+    /// Whether the field borrows from the input, as `&'a str`.
+    pub fn is_borrowed(&self) -> bool {
+        self.borrow
+            && (self.flatten
+                || !self.redact
+                    && self.type_override.is_none()
+                    && self.custom_type().is_none()
+                    && self.type_parser() == Some(TypeParser::String))
+    }
+
+    /// The custom type accepting all values seen, if any.
+    pub(crate) fn custom_type(&self) -> Option<String> {
+        if self.is_empty {
+            return None;
+        }
+
+        self.custom_types.pick(&self.valid_custom_types)
+    }
+
+    /// Whether the field holds a nested struct, flattened into its parent.
+    pub fn is_flattened(&self) -> bool {
+        self.flatten
+    }
+
+    /// Whether the field captures a column beyond the header row.
+    pub fn is_extra(&self) -> bool {
+        self.extra
+    }
+
+    /// The Rust type of the field, like `Option<u32>`.
+    pub fn type_name(&self) -> String {
+        // Masked values are text, whatever they looked like.
+        let type_override = match self.redact {
+            true => Some("String".to_string()),
+            false => self.type_override.clone().or_else(|| self.custom_type()),
+        };
+
+        if let Some(type_override) = type_override {
+            return if self.is_optional_or_empty() {
+                format!("Option<{type_override}>")
+            } else {
+                type_override
+            };
+        }
+
+        match self.type_parser() {
+            Some(_) if self.is_borrowed() && self.is_optional() => "Option<&'a str>".to_string(),
+            Some(_) if self.is_borrowed() => "&'a str".to_string(),
+            Some(parser) => parser.type_name(self.is_optional()).to_string(),
+            None => match self.empty_columns {
+                EmptyColumns::OptionString => "Option<String>".to_string(),
+                EmptyColumns::Unit | EmptyColumns::Skip => "Option<()>".to_string(),
+            },
+        }
+    }
+
+    pub fn new(field: &str, config: &Config) -> Self {
+        let naming = Naming {
+            case: config.field_case,
+            digit_prefix: config.digit_prefix.clone(),
+            allow_unicode: config.allow_unicode_idents,
+        };
+        let name = sanitize(field, &naming);
+
+        // User overrides are matched by either the raw or the sanitized name.
+        let type_override = config
+            .type_overrides
+            .get(field)
+            .or_else(|| config.type_overrides.get(&name))
+            .cloned();
+
+        let mut result = Field {
             name,
             raw_name: field.to_string(),
-            valid_parsers: TypeParser::all(),
-            optional: false,
+            valid_parsers: TypeParser::enabled(config),
+            valid_custom_types: (0..config.custom_types.len()).collect(),
+            type_override,
+            optional_override: None,
+            cells: 0,
+            empty_cells: 0,
+            optional_threshold: config.optional_threshold,
             is_empty: true,
+            empty_columns: config.empty_columns,
+            // Variants of split inputs are read from a copy of the record.
+            borrow: config.borrow && config.split_by.is_none(),
+            flatten: false,
+            nested: false,
+            extra: false,
+            naming,
+            custom_types: config.custom_types.clone(),
+            null_values: config.null_values.clone(),
+            redact: false,
+            // Diesel primary keys are picked among columns of distinct values.
+            stats: (config.stats_docs || config.with_validation || config.emit == Emit::Diesel)
+                .then(Stats::default),
+            eliminated: vec![],
+            samples: vec![],
+        };
+
+        result.redact = result.is_listed(&config.redact_columns);
+
+        if result.is_listed(&config.required_columns) {
+            result.optional_override = Some(false);
+        } else if config.all_optional || result.is_listed(&config.optional_columns) {
+            result.optional_override = Some(true);
+        }
+
+        result
+    }
+
+    /// A field holding the nested struct `type_name`, flattened into its parent.
+    pub fn flattened(name: &str, type_name: &str, borrowed: bool, config: &Config) -> Self {
+        let mut result = Field::new(name, config);
+        result.type_override = Some(type_name.to_string());
+        result.optional_override = Some(false);
+        result.borrow = borrowed;
+        result.flatten = true;
+        result.redact = false;
+        result.stats = None;
+        result
+    }
+
+    /// A column beyond the header row, `extra_1` for the first, captured as text.
+    pub fn extra(index: usize, config: &Config) -> Self {
+        let mut result = Field::new(&format!("extra_{index}"), config);
+        result.type_override = Some("String".to_string());
+        result.optional_override = Some(true);
+        result.extra = true;
+        result
+    }
+
+    /// Takes what was inferred from another field for the same column, keeping
+    /// how this one is configured.
+    pub fn inherit(&mut self, inferred: &Field) {
+        self.valid_parsers = inferred.valid_parsers.clone();
+        self.valid_custom_types = inferred.valid_custom_types.clone();
+        self.eliminated = inferred.eliminated.clone();
+        self.samples = inferred.samples.clone();
+        self.cells = inferred.cells;
+        self.empty_cells = inferred.empty_cells;
+        self.is_empty = inferred.is_empty;
+
+        if self.stats.is_some() {
+            self.stats = inferred.stats.clone();
+        }
+    }
+
+    /// The same field under a different name, still deserialized from its raw name.
+    pub fn renamed(mut self, name: &str) -> Self {
+        self.name = sanitize(name, &self.naming);
+        self
+    }
+
+    /// The same field, moved into a struct flattened into its parent.
+    pub fn nested(mut self) -> Self {
+        self.nested = true;
+        self
+    }
+}
+
+/// Fields for the given headers, with those sanitized to nothing named after
+/// their index, and no two sharing a name.
+pub fn for_headers(headers: &[String], config: &Config) -> Vec<Field> {
+    let mut fields = headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| {
+            let field = Field::new(header, config);
+            if field.name.is_empty() {
+                field.renamed(&format!("{}{i}", config.placeholder_prefix))
+            } else {
+                field
+            }
+        })
+        .collect::<Vec<_>>();
+
+    dedup(&mut fields);
+    fields
+}
+
+/// Renames fields whose names collide, because of repeated headers or
+/// headers sanitized to the same name, by appending `_2`, `_3` and so on.
+/// Raw names are kept, so each field still maps to its own column.
+fn dedup(fields: &mut [Field]) {
+    let names = fields.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+    let case = fields.first().map(|f| f.naming.case).unwrap_or_default();
+    let names = unique_names(&names, case);
+
+    for (field, name) in fields.iter_mut().zip(names) {
+        field.name = name;
+    }
+}
+
+fn unique_names(names: &[&str], case: FieldCase) -> Vec<String> {
+    let separator = if case == FieldCase::Camel { "" } else { "_" };
+    let mut taken = names.iter().map(|n| n.to_string()).collect::<HashSet<_>>();
+    let mut seen = HashSet::new();
+    let mut result = Vec::with_capacity(names.len());
+
+    for &name in names {
+        if seen.insert(name) {
+            result.push(name.to_string());
+            continue;
         }
+
+        // Suffixed keywords are no longer keywords.
+        let base = name.trim_start_matches("r#");
+        let unique = (2..)
+            .map(|n| format!("{base}{separator}{n}"))
+            .find(|n| !taken.contains(n))
+            .expect("Some suffix is free.");
+
+        taken.insert(unique.clone());
+        result.push(unique);
+    }
+
+    result
+}
+
+/// How field names are derived from headers.
+#[derive(Clone, Debug, Default)]
+struct Naming {
+    case: FieldCase,
+    /// Prepended to names starting with a digit.
+    digit_prefix: String,
+    /// Keep non-ASCII letters rather than transliterating them.
+    allow_unicode: bool,
+}
+
+fn sanitize(field: &str, naming: &Naming) -> String {
+    let case = naming.case;
+    let field = if naming.allow_unicode {
+        field.to_string()
+    } else {
+        deunicode::deunicode(field)
+    };
+
+    if case == FieldCase::Keep && syn::parse_str::<syn::Ident>(&field).is_ok() {
+        return field;
+    }
+
+    // Handle punctuation, and convert to the requested case.
+    let name = field
+        .replace(|c: char| !c.is_alphanumeric() && !c.is_whitespace(), "_")
+        .trim_start_matches('_')
+        .to_case(if case == FieldCase::Camel {
+            Case::Camel
+        } else {
+            Case::Snake
+        });
+
+    // Identifiers can't start with a digit.
+    let name = if name.starts_with(|c: char| !c.is_alphabetic()) {
+        format!("{}{name}", naming.digit_prefix)
+    } else {
+        name
+    };
+
+    // Check for reserved words.
+    match keywords::check(&name) {
+        Some(Keyword::Raw) => format!("r#{}", name),
+        Some(Keyword::NotRaw) => format!("{}_field", name),
+        None => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sanitize, unique_names, Naming};
+    use crate::FieldCase;
+
+    fn naming(case: FieldCase) -> Naming {
+        Naming {
+            case,
+            digit_prefix: "n_".to_string(),
+            allow_unicode: false,
+        }
+    }
+
+    #[test]
+    fn field_case() {
+        let snake = naming(FieldCase::Snake);
+        let camel = naming(FieldCase::Camel);
+        let keep = naming(FieldCase::Keep);
+
+        assert_eq!(sanitize("Order ID", &snake), "order_id");
+        assert_eq!(sanitize("Order ID", &camel), "orderId");
+        assert_eq!(sanitize("Order ID", &keep), "order_id");
+        assert_eq!(sanitize("OrderID", &keep), "OrderID");
+        assert_eq!(sanitize("type", &keep), "r#type");
+        assert_eq!(sanitize("Type", &camel), "r#type");
+        assert_eq!(sanitize("self", &snake), "self_field");
+        assert_eq!(sanitize("Self", &keep), "self_field");
+        assert_eq!(sanitize("crate", &camel), "crate_field");
+    }
+
+    #[test]
+    fn leading_digits() {
+        let snake = naming(FieldCase::Snake);
+
+        assert_eq!(sanitize("2023_revenue", &snake), "n_2023_revenue");
+        assert_eq!(sanitize("1st place", &snake), "n_1_st_place");
+        assert_eq!(sanitize("2023", &naming(FieldCase::Keep)), "n_2023");
+    }
+
+    #[test]
+    fn unicode() {
+        let snake = naming(FieldCase::Snake);
+        let unicode = Naming {
+            allow_unicode: true,
+            ..naming(FieldCase::Snake)
+        };
+
+        assert_eq!(sanitize("Straße", &snake), "strasse");
+        assert_eq!(sanitize("año", &snake), "ano");
+        assert_eq!(sanitize("名前", &snake), "ming_qian");
+        assert_eq!(sanitize("Straße", &unicode), "straße");
+        assert_eq!(sanitize("名前", &unicode), "名前");
+    }
+
+    #[test]
+    fn unique() {
+        let names = ["amount", "amount", "amount_2", "r#type", "r#type"];
+        assert_eq!(
+            unique_names(&names, FieldCase::Snake),
+            ["amount", "amount_3", "amount_2", "r#type", "type_2"]
+        );
+        assert_eq!(unique_names(&["id", "id"], FieldCase::Camel), ["id", "id2"]);
     }
 }
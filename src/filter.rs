@@ -0,0 +1,92 @@
+use std::str::FromStr;
+
+use csv::ByteRecord;
+
+/// A `column == "value"` or `column != "value"` condition rows must meet to
+/// take part in inference.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowFilter {
+    /// Raw header or field name.
+    pub column: String,
+    pub negated: bool,
+    pub value: String,
+}
+
+impl RowFilter {
+    pub fn matches(&self, record: &ByteRecord, index: usize) -> bool {
+        let value = record.get(index).unwrap_or_default();
+        (value == self.value.as_bytes()) != self.negated
+    }
+}
+
+impl FromStr for RowFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // The value may hold the other operator, so split at the first one.
+        let (index, negated) = match (s.find("=="), s.find("!=")) {
+            (Some(eq), Some(ne)) if ne < eq => (ne, true),
+            (Some(eq), _) => (eq, false),
+            (None, Some(ne)) => (ne, true),
+            (None, None) => {
+                return Err(format!(
+                    "expected COLUMN == VALUE or COLUMN != VALUE, got '{s}'"
+                ))
+            }
+        };
+        let (column, value) = (&s[..index], &s[index + 2..]);
+
+        let column = column.trim();
+        if column.is_empty() {
+            return Err(format!("missing column name in '{s}'"));
+        }
+
+        // Quotes are optional, but allow for surrounding whitespace in the value.
+        let value = value.trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .unwrap_or(value);
+
+        Ok(RowFilter {
+            column: column.to_string(),
+            negated,
+            value: value.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RowFilter;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            r#"country == "US""#.parse(),
+            Ok(RowFilter {
+                column: "country".to_string(),
+                negated: false,
+                value: "US".to_string(),
+            })
+        );
+        assert_eq!(
+            "Record Type!= refund ".parse(),
+            Ok(RowFilter {
+                column: "Record Type".to_string(),
+                negated: true,
+                value: "refund".to_string(),
+            })
+        );
+        assert_eq!(
+            "note==a!=b".parse(),
+            Ok(RowFilter {
+                column: "note".to_string(),
+                negated: false,
+                value: "a!=b".to_string(),
+            })
+        );
+        assert!("country".parse::<RowFilter>().is_err());
+        assert!(r#"== "US""#.parse::<RowFilter>().is_err());
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::VecDeque;
+
+use csv::ByteRecord;
+use regex::Regex;
+
+use crate::Error;
+
+/// Drops the last `count` records, then any trailing records matching `pattern`.
+///
+/// Records are matched against their fields joined with commas, so `^TOTAL`
+/// matches rows whose first field starts with `TOTAL`.
+pub struct SkipFooter<I> {
+    records: I,
+    count: usize,
+    pattern: Option<Regex>,
+    // Records held back until we know they are not part of the footer,
+    // along with whether they match the pattern.
+    pending: VecDeque<(ByteRecord, bool)>,
+}
+
+impl<I> SkipFooter<I> {
+    pub fn new(records: I, count: usize, pattern: Option<Regex>) -> Self {
+        SkipFooter {
+            records,
+            count,
+            pattern,
+            pending: VecDeque::new(),
+        }
+    }
+
+    fn is_footer(&self, record: &ByteRecord) -> bool {
+        self.pattern
+            .as_ref()
+            .is_some_and(|p| p.is_match(&crate::records::lossy(record).join(",")))
+    }
+
+    /// Number of pending records known not to be part of the footer.
+    fn releasable(&self) -> usize {
+        let candidates = self.pending.len().saturating_sub(self.count);
+
+        self.pending
+            .range(..candidates)
+            .rposition(|(_, is_footer)| !is_footer)
+            .map_or(0, |i| i + 1)
+    }
+}
+
+impl<I> Iterator for SkipFooter<I>
+where
+    I: Iterator<Item = Result<ByteRecord, Error>>,
+{
+    type Item = Result<ByteRecord, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.releasable() == 0 {
+            match self.records.next()? {
+                Ok(record) => {
+                    let is_footer = self.is_footer(&record);
+                    self.pending.push_back((record, is_footer));
+                }
+                // Errors are never held back.
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        self.pending.pop_front().map(|(record, _)| Ok(record))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SkipFooter;
+    use csv::ByteRecord;
+    use regex::Regex;
+
+    fn skip(rows: &[&str], count: usize, pattern: Option<&str>) -> Vec<String> {
+        let records = rows.iter().map(|row| Ok(ByteRecord::from(vec![*row])));
+        let pattern = pattern.map(|p| Regex::new(p).unwrap());
+
+        SkipFooter::new(records, count, pattern)
+            .map(|r| String::from_utf8_lossy(&r.unwrap()[0]).into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn skip_footer() {
+        let rows = ["1", "TOTAL 1", "2", "SUBTOTAL", "TOTAL", "Exported by X"];
+
+        assert_eq!(skip(&rows, 0, None), rows);
+        assert_eq!(skip(&rows, 1, None), rows[..5]);
+        assert_eq!(skip(&rows, 1, Some("TOTAL")), ["1", "TOTAL 1", "2"]);
+        assert_eq!(skip(&rows, 0, Some("TOTAL")), rows);
+        assert_eq!(skip(&rows, 10, None), Vec::<String>::new());
+    }
+}
@@ -57,6 +57,24 @@ const RESERVED_KEYWORDS: phf::Set<&'static str> = phf_set! {
     "yield",
 };
 
-pub fn check(word: &str) -> bool {
-    RESERVED_KEYWORDS.contains(word)
+/// Keywords that can't be used as raw identifiers either.
+const PATH_KEYWORDS: [&str; 4] = ["self", "Self", "crate", "super"];
+
+/// How a reserved word can still be used as an identifier.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Keyword {
+    /// As a raw identifier, like `r#type`.
+    Raw,
+    /// Not at all, so it has to be renamed.
+    NotRaw,
+}
+
+pub fn check(word: &str) -> Option<Keyword> {
+    if PATH_KEYWORDS.contains(&word) {
+        Some(Keyword::NotRaw)
+    } else if RESERVED_KEYWORDS.contains(word) {
+        Some(Keyword::Raw)
+    } else {
+        None
+    }
 }
@@ -1,43 +1,902 @@
 use std::io::Read;
 
-pub use error::Error;
-use field::Field;
+use csv::ByteRecord;
+use rayon::prelude::*;
 
+pub use codegen::RustStructs;
+use codegen::Sample;
+pub use config::{Config, ConfigBuilder};
+pub use diff::{diff, Change};
+pub use distinct::Distinct;
+pub use emit::Emitter;
+pub use error::{Error, RecordContext};
+pub use fake::fake;
+pub use field::{Elimination, Field};
+pub use filter::RowFilter;
+use footer::SkipFooter;
+use records::Records;
+pub use report::report;
+pub use sampling::Sampling;
+pub use schema::{Schema, Table};
+pub use stats::Stats;
+pub use type_infer::{TypeInfer, TypeRegistry};
+pub use type_parser::TypeParser;
+
+// Writing files isn't supported in the browser.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod build;
+#[cfg(feature = "cli")]
+#[doc(hidden)]
+pub mod cli;
 mod codegen;
+mod config;
+mod diff;
+mod distinct;
+mod emit;
 mod error;
+mod fake;
 mod field;
+mod filter;
+mod footer;
 mod keywords;
+mod records;
+mod report;
+mod sampling;
+mod schema;
+mod stats;
+mod type_infer;
 mod type_parser;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+/// What to do with fields beyond the header row.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(any(feature = "cli", feature = "wasm"), derive(clap::ValueEnum))]
+pub enum ExtraColumns {
+    /// Leave them out of inference, with a warning.
+    #[default]
+    Ignore,
+    /// Fail on the first record with any.
+    Error,
+    /// Add `extra_1`, `extra_2`, … columns holding them, as `Option<String>`,
+    /// named in the headers by generated readers.
+    Capture,
+}
+
+/// How to generate columns for which no values were seen.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(any(feature = "cli", feature = "wasm"), derive(clap::ValueEnum))]
+pub enum EmptyColumns {
+    /// Generate `Option<()>`.
+    #[default]
+    Unit,
+    /// Generate `Option<String>`.
+    OptionString,
+    /// Omit the column from the struct.
+    Skip,
+}
+
+/// Visibility of the generated types and their fields.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(any(feature = "cli", feature = "wasm"), derive(clap::ValueEnum))]
+pub enum Visibility {
+    /// `pub`
+    #[default]
+    Pub,
+    /// `pub(crate)`
+    PubCrate,
+    /// No visibility modifier.
+    Private,
+}
+
+/// Where leading and trailing whitespace is trimmed, both when inferring
+/// and in generated readers. Untrimmed whitespace-only cells are values, not
+/// empty cells.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(any(feature = "cli", feature = "wasm"), derive(clap::ValueEnum))]
+pub enum Trim {
+    #[default]
+    All,
+    Headers,
+    Fields,
+    None,
+}
+
+impl From<Trim> for csv::Trim {
+    fn from(trim: Trim) -> Self {
+        match trim {
+            Trim::All => csv::Trim::All,
+            Trim::Headers => csv::Trim::Headers,
+            Trim::Fields => csv::Trim::Fields,
+            Trim::None => csv::Trim::None,
+        }
+    }
+}
+
+/// Naming convention of the generated field names.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(any(feature = "cli", feature = "wasm"), derive(clap::ValueEnum))]
+pub enum FieldCase {
+    /// `order_id`
+    #[default]
+    Snake,
+    /// `orderId`
+    Camel,
+    /// The header as is, where it's a valid identifier, and snake_case otherwise.
+    Keep,
+}
+
+/// Naming convention of type names derived from file names.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(any(feature = "cli", feature = "wasm"), derive(clap::ValueEnum))]
+pub enum StructCase {
+    /// `OrderLines`
+    #[default]
+    Pascal,
+    /// The file name as is, with anything but letters and digits replaced by `_`.
+    Keep,
+}
+
+/// Format of the generated output.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(any(feature = "cli", feature = "wasm"), derive(clap::ValueEnum))]
+pub enum Emit {
+    /// Rust structs deriving `Deserialize`.
+    #[default]
+    Rust,
+    /// SQL `CREATE TABLE` statements.
+    Sql,
+    /// A JSON Schema describing a row.
+    JsonSchema,
+    /// A function building the `arrow` schema of a row.
+    Arrow,
+    /// Functions building a Polars schema, and scanning the file with it.
+    Polars,
+    /// A Diesel `table!`, and a struct to query and insert its rows.
+    Diesel,
+    /// A struct deriving `sqlx::FromRow`.
+    Sqlx,
+    /// A TypeScript interface.
+    Typescript,
+    /// A pydantic model.
+    Pydantic,
+    /// A Python dataclass.
+    Dataclass,
+    /// A Protocol Buffers message.
+    Proto,
+    /// An Avro record schema.
+    Avro,
+}
+
+impl Emit {
+    /// How line comments start in this format, if it has them.
+    pub fn line_comment(&self) -> Option<&'static str> {
+        match self {
+            Emit::Sql => Some("--"),
+            Emit::Pydantic | Emit::Dataclass => Some("#"),
+            Emit::JsonSchema | Emit::Avro => None,
+            _ => Some("//"),
+        }
+    }
+}
+
+/// Layout of the report on how each column's type was inferred.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(any(feature = "cli", feature = "wasm"), derive(clap::ValueEnum))]
+pub enum ReportFormat {
+    /// Aligned text, one block per column.
+    #[default]
+    Table,
+    /// An array of objects, one per column.
+    Json,
+}
+
+/// Dialect of the generated SQL, deciding column types and identifier quoting.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(any(feature = "cli", feature = "wasm"), derive(clap::ValueEnum))]
+pub enum SqlDialect {
+    #[default]
+    Postgres,
+    Mysql,
+    Sqlite,
+}
+
+/// Generates code for any source of CSV, like a file, a socket or a byte slice.
+pub fn run<T: Read>(reader: csv::Reader<T>, config: &Config) -> Result<String, Error> {
+    generate(&infer(reader, config)?, config)
+}
+
+/// Generates code for CSV held in a string, read with [`Config::reader_builder`].
+pub fn run_on_str(csv: &str, config: &Config) -> Result<String, Error> {
+    run(config.reader_builder().from_reader(csv.as_bytes()), config)
+}
+
+/// Like [`run`], but renders the inferred schema with the given emitter
+/// instead of the one selected by `config.emit`.
+pub fn run_with<T: Read>(
+    reader: csv::Reader<T>,
+    config: &Config,
+    emitter: &dyn Emitter,
+) -> Result<String, Error> {
+    emitter.emit(&infer(reader, config)?, config)
+}
+
+/// Generates one struct per input, named by the paired name rather than
+/// `config.struct_name`.
+pub fn run_all<T: Read>(
+    inputs: Vec<(String, csv::Reader<T>)>,
+    config: &Config,
+) -> Result<String, Error> {
+    generate(&infer_all(inputs, config)?, config)
+}
+
+/// Generates a single struct for inputs sharing the same headers, inferring
+/// types over the rows of all of them.
+pub fn run_merged<T: Read>(readers: Vec<csv::Reader<T>>, config: &Config) -> Result<String, Error> {
+    generate(&infer_merged(readers, config)?, config)
+}
+
+/// Renders a schema in the format selected by `config.emit`.
+pub fn generate(schema: &Schema, config: &Config) -> Result<String, Error> {
+    config.emit.emit(schema, config)
+}
 
-pub struct Config {
-    pub lines: usize,
-    pub min_fields: usize,
-    pub struct_name: String,
-    pub blank_lines: usize,
+/// Infers a single table, named `config.struct_name`.
+pub fn infer<T: Read>(reader: csv::Reader<T>, config: &Config) -> Result<Schema, Error> {
+    infer_all(vec![(config.struct_name.clone(), reader)], config)
 }
 
-pub fn run<T: Read>(mut reader: csv::Reader<T>, config: &Config) -> Result<String, Error> {
-    let mut fields: Vec<Field> = reader
-        .headers()
-        .map_err(Error::CantParseFieldHeaders)?
+/// Infers a table per input, named by the paired name. Inputs are read in
+/// turn, so on an error the last one taken is where it is.
+pub fn infer_all<T: Read>(
+    inputs: impl IntoIterator<Item = (String, csv::Reader<T>)>,
+    config: &Config,
+) -> Result<Schema, Error> {
+    let tables = inputs
+        .into_iter()
+        .map(|(name, reader)| {
+            let (groups, sample) = infer_groups(reader, config)?;
+            Ok(Table {
+                name,
+                groups,
+                delimiter: None,
+                sample,
+            })
+        })
+        .collect::<Result<_, Error>>()?;
+
+    Ok(Schema { tables })
+}
+
+/// Infers a single table, named `config.struct_name`, over the rows of
+/// inputs sharing the same headers. Inputs are read in turn, as with
+/// [`infer_all`].
+pub fn infer_merged<T: Read>(
+    readers: impl IntoIterator<Item = csv::Reader<T>>,
+    config: &Config,
+) -> Result<Schema, Error> {
+    let mut merged: Option<Groups> = None;
+    let mut first_sample = None;
+
+    for reader in readers {
+        let (groups, sample) = infer_groups(reader, config)?;
+        first_sample.get_or_insert(sample);
+
+        let Some(merged) = merged.as_mut() else {
+            merged = Some(groups);
+            continue;
+        };
+
+        for (key, fields) in groups {
+            let Some((_, merged)) = merged.iter_mut().find(|(k, _)| *k == key) else {
+                merged.push((key, fields));
+                continue;
+            };
+
+            let expected = merged.iter().map(|f| f.raw_name.as_str());
+            let found = fields.iter().map(|f| f.raw_name.as_str());
+            if !expected.eq(found) {
+                return Err(Error::MismatchedHeaders {
+                    expected: merged.iter().map(|f| f.raw_name.clone()).collect(),
+                    found: fields.iter().map(|f| f.raw_name.clone()).collect(),
+                });
+            }
+
+            for (field, other) in merged.iter_mut().zip(&fields) {
+                field.merge(other);
+            }
+        }
+    }
+
+    Ok(Schema {
+        tables: vec![Table {
+            name: config.struct_name.clone(),
+            groups: merged.unwrap_or_default(),
+            delimiter: None,
+            sample: first_sample.unwrap_or_default(),
+        }],
+    })
+}
+
+/// Fields inferred for each value of the `config.split_by` column, or for
+/// the whole input under an empty key.
+type Groups = Vec<(String, Vec<Field>)>;
+
+/// Records handed to the worker threads at a time, with `config.jobs`.
+const BATCH_ROWS: usize = 64 * 1024;
+
+/// Narrows the fields of the record's group, starting it from `fields` if it's
+/// new. Returns whether anything changed, and counts fields beyond the header row.
+fn update_groups(
+    groups: &mut Groups,
+    fields: &[Field],
+    split_index: Option<usize>,
+    record: &ByteRecord,
+    config: &Config,
+    extra_fields: &mut usize,
+) -> Result<bool, Error> {
+    let mut changed = false;
+    let trim = matches!(config.trim, Trim::All | Trim::Fields);
+    let headers = fields.len();
+
+    let key = match split_index {
+        Some(i) => records::cell(record, i, &fields[i].raw_name, trim)?,
+        None => "",
+    };
+    let line = record.position().map_or(0, |p| p.line());
+    let fields = match groups.iter().position(|(k, _)| k == key) {
+        Some(i) => &mut groups[i].1,
+        None => {
+            if split_index.is_some() {
+                log::trace!("new group {key:?} on line {line}");
+            }
+            changed = true;
+            groups.push((key.to_string(), fields.to_vec()));
+            &mut groups.last_mut().unwrap().1
+        }
+    };
+
+    if record.len() > headers {
+        match config.extra_columns {
+            ExtraColumns::Ignore => *extra_fields += record.len() - headers,
+            ExtraColumns::Error => {
+                return Err(Error::TooManyFields {
+                    context: records::context(record, None),
+                    expected: headers,
+                })
+            }
+            ExtraColumns::Capture => {
+                while fields.len() < record.len() {
+                    fields.push(Field::extra(fields.len() - headers + 1, config));
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    for (i, f) in fields.iter_mut().enumerate().take(record.len()) {
+        let cell = record.get(i).unwrap_or_default();
+        changed |= f
+            .update_bytes(cell, trim, line)
+            .map_err(|source| Error::InvalidUtf8 {
+                context: records::context(record, Some((i, f.raw_name.clone()))),
+                source,
+            })?;
+    }
+
+    // Ragged rows missing trailing fields make those columns optional.
+    for f in fields.iter_mut().skip(record.len()) {
+        changed |= f.update_at("", line);
+    }
+
+    Ok(changed)
+}
+
+/// How many records are embedded in generated tests.
+const SAMPLE_ROWS: usize = 5;
+
+/// Warns about fields whose share of empty cells is within a factor of two of
+/// the threshold, on either side, as their optionality may be down to chance.
+fn report_near_threshold(groups: &Groups, threshold: f64) {
+    for field in groups.iter().flat_map(|(_, fields)| fields) {
+        let fraction = field.empty_fraction();
+        if fraction == 0.0 || fraction > threshold * 2.0 || fraction < threshold / 2.0 {
+            continue;
+        }
+
+        let (verdict, side) = if fraction > threshold {
+            ("optional", "above")
+        } else {
+            ("required", "below")
+        };
+        log::warn!(
+            "column '{}' is {verdict} with {:.3}% empty cells, just {side} the threshold of {:.3}%",
+            field.raw_name,
+            fraction * 100.0,
+            threshold * 100.0
+        );
+    }
+}
+
+fn infer_groups<T: Read>(
+    mut reader: csv::Reader<T>,
+    config: &Config,
+) -> Result<(Groups, Sample), Error> {
+    let has_headers = reader.has_headers();
+
+    // Without a header row, this is the first record, which is still yielded as data.
+    // With one, it is consumed as headers even when replaced by `config.headers`.
+    let first_row = reader.headers().map_err(Error::CantParseFieldHeaders)?;
+    let file_headers = first_row.iter().map(String::from).collect::<Vec<_>>();
+
+    let header_names: Vec<String> = if !config.headers.is_empty() {
+        if config.headers.len() != first_row.len() {
+            return Err(Error::HeaderCountMismatch {
+                expected: first_row.len(),
+                found: config.headers.len(),
+            });
+        }
+
+        config.headers.clone()
+    } else if has_headers {
+        first_row.iter().map(String::from).collect()
+    } else {
+        (0..first_row.len()).map(|i| format!("field_{i}")).collect()
+    };
+
+    let fields = field::for_headers(&header_names, config);
+
+    // First occurrences of headers seen again later.
+    let repeated = header_names
+        .iter()
+        .enumerate()
+        .filter(|&(i, h)| !header_names[..i].contains(h) && header_names[i + 1..].contains(h))
+        .map(|(_, h)| format!("'{h}'"))
+        .collect::<Vec<_>>();
+    if !repeated.is_empty() {
+        log::warn!(
+            "repeated headers {}; records must be deserialized by position, as generated readers do, e.g. with `StringRecord::deserialize(None)`",
+            repeated.join(", ")
+        );
+    }
+
+    let filters = config
+        .filters
         .iter()
-        .map(Field::from)
-        .collect();
+        .map(|filter| {
+            fields
+                .iter()
+                .position(|f| f.is_listed(std::slice::from_ref(&filter.column)))
+                .map(|index| (filter, index))
+                .ok_or_else(|| Error::UnknownFilterColumn(filter.column.clone()))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
 
-    for record in reader.records().take(config.lines) {
-        let record = record.map_err(Error::CantParseRecord)?;
+    let split_index = match &config.split_by {
+        Some(column) => Some(
+            fields
+                .iter()
+                .position(|f| f.is_listed(std::slice::from_ref(column)))
+                .ok_or_else(|| Error::UnknownSplitColumn(column.clone()))?,
+        ),
+        None => None,
+    };
+    let mut groups: Groups = vec![];
+    // The file's own header row, replaced again by generated tests.
+    let mut sample = Sample {
+        headers: has_headers.then_some(file_headers),
+        records: vec![],
+    };
 
-        if config.min_fields > 0 {
+    let mut extra_fields = 0;
+
+    let keep = |record: &ByteRecord| {
+        if config.min_fields > 0 || config.max_fields.is_some() {
             let len = record.iter().filter(|s| !s.is_empty()).count();
-            if len <= config.min_fields {
+            if len <= config.min_fields || config.max_fields.is_some_and(|max| len > max) {
+                return false;
+            }
+        }
+        if let Some(pattern) = &config.skip_matching {
+            if pattern.is_match(&records::lossy(record).join(",")) {
+                return false;
+            }
+        }
+        filters.iter().all(|(f, index)| f.matches(record, *index))
+    };
+
+    let mut keep_sample = |record: &ByteRecord| {
+        if config.with_tests && sample.records.len() < SAMPLE_ROWS {
+            sample.records.push(records::lossy(record));
+        }
+    };
+
+    // Narrows the groups for one record, returning whether to stop early.
+    let mut stable_rows = 0;
+    let mut step = |record: &ByteRecord, groups: &mut Groups, extra_fields: &mut usize| {
+        keep_sample(record);
+        let changed = update_groups(groups, &fields, split_index, record, config, extra_fields)?;
+
+        stable_rows = if changed { 0 } else { stable_rows + 1 };
+        let converged = config.converge.is_some_and(|window| stable_rows >= window);
+        if converged {
+            log::debug!("no types changed over the last {stable_rows} records, stopping early");
+        }
+        Ok::<_, Error>(converged)
+    };
+
+    // Records are read into the same buffer one at a time, unless they have
+    // to be held on to: to skip a footer, sample at random, or be handed to
+    // worker threads in batches.
+    let parallel = config.jobs != 1 && config.converge.is_none();
+    let streaming = config.skip_footer == 0
+        && config.footer_pattern.is_none()
+        && !matches!(config.sample, Sampling::Random(_))
+        && !parallel;
+
+    if streaming {
+        let step_by = match config.sample {
+            Sampling::Every(n) => n,
+            _ => 1,
+        };
+        let mut record = ByteRecord::new();
+        let (mut read, mut kept) = (0, 0);
+
+        while read < config.lines {
+            let more =
+                reader
+                    .read_byte_record(&mut record)
+                    .map_err(|source| Error::CantParseRecord {
+                        context: records::context(&record, None),
+                        source,
+                    })?;
+            if !more {
+                break;
+            }
+            read += 1;
+
+            if !keep(&record) {
+                continue;
+            }
+            kept += 1;
+            if (kept - 1) % step_by != 0 {
                 continue;
             }
+
+            if step(&record, &mut groups, &mut extra_fields)? {
+                break;
+            }
+        }
+    } else {
+        let records = Records::new(&mut reader);
+        let records = SkipFooter::new(records, config.skip_footer, config.footer_pattern.clone())
+            .take(config.lines)
+            .filter(|record| record.as_ref().map_or(true, keep));
+        let mut records = sampling::sample(records, config.sample)?;
+
+        if parallel {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(config.jobs)
+                .build()
+                .map_err(Error::CantStartThreads)?;
+            let chunk_size = BATCH_ROWS / pool.current_num_threads();
+
+            loop {
+                let batch = records
+                    .by_ref()
+                    .take(BATCH_ROWS)
+                    .collect::<Result<Vec<_>, Error>>()?;
+                if batch.is_empty() {
+                    break;
+                }
+                batch.iter().for_each(&mut keep_sample);
+                log::trace!(
+                    "inferring a batch of {} records on {} threads",
+                    batch.len(),
+                    pool.current_num_threads()
+                );
+
+                let partial = pool.install(|| {
+                    batch
+                        .par_chunks(chunk_size)
+                        .map(|chunk| {
+                            let (mut groups, mut extra_fields) = (vec![], 0);
+                            for record in chunk {
+                                update_groups(
+                                    &mut groups,
+                                    &fields,
+                                    split_index,
+                                    record,
+                                    config,
+                                    &mut extra_fields,
+                                )?;
+                            }
+                            Ok((groups, extra_fields))
+                        })
+                        .collect::<Result<Vec<_>, Error>>()
+                })?;
+
+                // Chunks are merged in order, so groups are still listed as first seen.
+                for (chunk_groups, chunk_extra_fields) in partial {
+                    extra_fields += chunk_extra_fields;
+                    for (key, chunk_fields) in chunk_groups {
+                        match groups.iter_mut().find(|(k, _)| *k == key) {
+                            Some((_, fields)) => {
+                                for (field, other) in fields.iter_mut().zip(&chunk_fields) {
+                                    field.merge(other);
+                                }
+                                // Captured columns first seen in this chunk.
+                                fields.extend(chunk_fields.into_iter().skip(fields.len()));
+                            }
+                            None => groups.push((key, chunk_fields)),
+                        }
+                    }
+                }
+            }
+        } else {
+            for record in records {
+                if step(&record?, &mut groups, &mut extra_fields)? {
+                    break;
+                }
+            }
+        }
+    }
+
+    if extra_fields > 0 {
+        log::warn!("ignored {extra_fields} fields beyond the header row");
+    }
+
+    // Groups without the widest records still need every captured column.
+    let widest = groups.iter().map(|(_, f)| f.len()).max().unwrap_or(0);
+    if widest > fields.len() {
+        for (_, group) in &mut groups {
+            while group.len() < widest {
+                group.push(Field::extra(group.len() - fields.len() + 1, config));
+            }
         }
+        log::warn!(
+            "captured fields beyond the header row as extra_1 to extra_{}; generated readers name them in the headers, other records must be deserialized by position, e.g. with `StringRecord::deserialize(None)`",
+            widest - fields.len()
+        );
+    }
+
+    if config.optional_threshold > 0.0 {
+        report_near_threshold(&groups, config.optional_threshold);
+    }
+
+    // Without any records, there is still a struct to generate.
+    if groups.is_empty() && split_index.is_none() {
+        groups.push((String::new(), fields));
+    }
+
+    Ok((groups, sample))
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use crate::{generate, infer_all, run, run_all, run_on_str, Config, Error, ExtraColumns};
+
+    #[test]
+    fn short_records() {
+        let csv = indoc! {"
+            id,name,score,note
+            1,a,5,x
+            2,b
+            3,c,7
+            4
+        "};
+        let expected = indoc! {"
+            #[derive(Debug, Deserialize)]
+            pub struct Record {
+                pub id: u8,
+                pub name: Option<char>,
+                pub score: Option<u8>,
+                pub note: Option<char>,
+            }
+        "};
+
+        for jobs in [1usize, 2] {
+            let config = Config::builder()
+                .flexible(true)
+                .jobs(jobs)
+                .with_imports(false)
+                .blank_lines(0usize)
+                .build();
+            assert_eq!(run_on_str(csv, &config).unwrap(), expected);
+        }
+
+        // Without `flexible`, a short record is an error pointing at it.
+        assert!(matches!(
+            run_on_str(csv, &Config::default()),
+            Err(Error::CantParseRecord { context, .. }) if context.line == 3
+        ));
+    }
 
-        for (i, field) in record.iter().enumerate() {
-            fields.get_mut(i).unwrap().update_for(field);
+    #[test]
+    fn skipped_records() {
+        let csv = indoc! {"
+            id,name,total
+            Section A,,
+            1,a,5
+            id,name,total
+            2,b,x,note
+            Page 1 of 2,,
+        "};
+        let config = Config::builder()
+            .flexible(true)
+            .min_fields(1usize)
+            .max_fields(3)
+            .skip_matching(regex::Regex::new("^id,").unwrap())
+            .with_imports(false)
+            .blank_lines(0usize)
+            .build();
+
+        assert_eq!(
+            run_on_str(csv, &config).unwrap(),
+            indoc! {"
+                #[derive(Debug, Deserialize)]
+                pub struct Record {
+                    pub id: u8,
+                    pub name: char,
+                    pub total: u8,
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn extra_columns() {
+        let csv = "id,name\n1,a\n2,b,x,y\n";
+        let config = |extra_columns| {
+            Config::builder()
+                .flexible(true)
+                .extra_columns(extra_columns)
+                .with_imports(false)
+                .blank_lines(0usize)
+                .build()
+        };
+
+        assert!(matches!(
+            run_on_str(csv, &config(ExtraColumns::Error)),
+            Err(Error::TooManyFields { context, expected: 2 }) if context.line == 3
+        ));
+
+        assert_eq!(
+            run_on_str(csv, &config(ExtraColumns::Capture)).unwrap(),
+            indoc! {"
+                #[derive(Debug, Deserialize)]
+                pub struct Record {
+                    pub id: u8,
+                    pub name: char,
+                    pub extra_1: Option<String>,
+                    pub extra_2: Option<String>,
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn numbers_then_text() {
+        // Enough numbers to fill the samples, so later ones are only counted.
+        let mut csv = String::from("id,code\n");
+        for i in 0..20 {
+            csv.push_str(&format!("{i},{i}\n"));
         }
+        csv.push_str("20,x1\n");
+        let config = Config::builder()
+            .with_imports(false)
+            .blank_lines(0usize)
+            .build();
+
+        assert_eq!(
+            run_on_str(&csv, &config).unwrap(),
+            indoc! {"
+                #[derive(Debug, Deserialize)]
+                pub struct Record {
+                    pub id: u8,
+                    pub code: String,
+                }
+            "}
+        );
+
+        let bytes = b"id,name\n1,a\n2,\xff\n";
+        let reader = config.reader_builder().from_reader(&bytes[..]);
+        assert!(matches!(
+            run(reader, &config),
+            Err(Error::InvalidUtf8 { context, .. }) if context.line == 3
+        ));
     }
 
-    codegen::generate(config, fields)
+    #[test]
+    fn converge() {
+        let csv = indoc! {"
+            id,code
+            1,1
+            2,
+            3,300
+            4,4
+            5,5
+            6,x
+        "};
+        let config = |converge| {
+            Config::builder()
+                .converge(converge)
+                .with_imports(false)
+                .blank_lines(0usize)
+                .build()
+        };
+        let code = |converge| {
+            let code = run_on_str(csv, &config(converge)).unwrap();
+            code.lines()
+                .find(|l| l.contains("code:"))
+                .unwrap()
+                .trim()
+                .to_string()
+        };
+
+        // Records 2 and 3 change the column, so two stable ones stop after record 5.
+        assert_eq!(code(Some(2)), "pub code: Option<u16>,");
+        assert_eq!(code(Some(3)), "pub code: Option<String>,");
+        assert_eq!(code(None), "pub code: Option<String>,");
+    }
+
+    #[test]
+    fn multiple_inputs() {
+        let config = Config::builder()
+            .with_imports(false)
+            .blank_lines(0usize)
+            .build();
+        let inputs = [
+            ("Orders", "id,total\n1,9.5\n"),
+            ("Customers", "id,name\n1,Ann\n"),
+        ]
+        .map(|(name, csv)| {
+            let reader = config.reader_builder().from_reader(csv.as_bytes());
+            (name.to_string(), reader)
+        });
+
+        assert_eq!(
+            run_all(inputs.into(), &config).unwrap(),
+            indoc! {"
+                #[derive(Debug, Deserialize)]
+                pub struct Orders {
+                    pub id: u8,
+                    pub total: f32,
+                }
+
+                #[derive(Debug, Deserialize)]
+                pub struct Customers {
+                    pub id: u8,
+                    pub name: String,
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn delimiter_per_input() {
+        let config = Config::builder()
+            .with_imports(false)
+            .with_reader(true)
+            .build();
+        let semicolons = Config::builder().delimiter(b';').build();
+        let inputs = [
+            ("A", "id,name\n1,a\n", &config),
+            ("B", "x;y\n1;2\n", &semicolons),
+        ]
+        .map(|(name, csv, config)| {
+            let reader = config.reader_builder().from_reader(csv.as_bytes());
+            (name.to_string(), reader)
+        });
+
+        let mut schema = infer_all(inputs, &config).unwrap();
+        schema.tables[1].delimiter = Some(b';');
+        let code = generate(&schema, &config).unwrap();
+
+        let (a, b) = code.split_once("pub fn read_bs").unwrap();
+        assert!(a.contains(".delimiter(b',')"), "{code}");
+        assert!(b.contains(".delimiter(b';')"), "{code}");
+    }
 }
@@ -0,0 +1,74 @@
+use std::io::Read;
+
+use csv::ByteRecord;
+
+use crate::{error::RecordContext, Error};
+
+/// Reads the records of `reader` as bytes, leaving UTF-8 validation to the
+/// cells that need it, and pointing errors at the offending record. Each
+/// record is its own copy, for when they are held on to; otherwise, read
+/// them into a single buffer with `read_byte_record`.
+pub struct Records<'r, R> {
+    reader: &'r mut csv::Reader<R>,
+    record: ByteRecord,
+}
+
+impl<'r, R: Read> Records<'r, R> {
+    pub fn new(reader: &'r mut csv::Reader<R>) -> Self {
+        Records {
+            reader,
+            record: ByteRecord::new(),
+        }
+    }
+}
+
+impl<R: Read> Iterator for Records<'_, R> {
+    type Item = Result<ByteRecord, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.reader.read_byte_record(&mut self.record) {
+            Ok(false) => None,
+            Ok(true) => Some(Ok(self.record.clone())),
+            Err(source) => Some(Err(Error::CantParseRecord {
+                context: context(&self.record, None),
+                source,
+            })),
+        }
+    }
+}
+
+/// The cell at `index` of `record` as text. Byte records are only trimmed of
+/// ASCII whitespace, so with `trim`, this also trims Unicode whitespace.
+pub fn cell<'a>(
+    record: &'a ByteRecord,
+    index: usize,
+    header: &str,
+    trim: bool,
+) -> Result<&'a str, Error> {
+    let bytes = record.get(index).unwrap_or_default();
+    let text = std::str::from_utf8(bytes).map_err(|source| Error::InvalidUtf8 {
+        context: context(record, Some((index, header.to_string()))),
+        source,
+    })?;
+
+    Ok(if trim { text.trim() } else { text })
+}
+
+/// Fields of `record` as text, replacing invalid UTF-8.
+pub fn lossy(record: &ByteRecord) -> Vec<String> {
+    record
+        .iter()
+        .map(|field| String::from_utf8_lossy(field).into_owned())
+        .collect()
+}
+
+pub fn context(record: &ByteRecord, column: Option<(usize, String)>) -> Box<RecordContext> {
+    let position = record.position();
+
+    Box::new(RecordContext {
+        line: position.map_or(0, |p| p.line()),
+        byte: position.map_or(0, |p| p.byte()),
+        column,
+        fields: lossy(record),
+    })
+}
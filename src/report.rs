@@ -0,0 +1,139 @@
+use serde_json::json;
+
+use crate::{ReportFormat, Schema};
+
+/// Describes, per column, how its type was inferred: the type chosen, the
+/// types ruled out and by which value, how many cells were empty, and a few
+/// of the values seen.
+pub fn report(schema: &Schema, format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Table => table(schema),
+        ReportFormat::Json => json(schema),
+    }
+}
+
+fn table(schema: &Schema) -> String {
+    let mut out = String::new();
+
+    for table in &schema.tables {
+        out.push_str(&format!("{}\n", table.name));
+
+        let fields = table.fields();
+        let width = fields
+            .iter()
+            .map(|f| f.raw_name.chars().count() + 2)
+            .max()
+            .unwrap_or(0);
+        let indent = " ".repeat(width + 4);
+
+        for field in &fields {
+            let header = format!("{:?}", field.raw_name);
+            out.push_str(&format!(
+                "  {header:width$}  {}: {}\n",
+                field.name,
+                field.type_name()
+            ));
+            out.push_str(&format!(
+                "{indent}nulls: {} of {}\n",
+                field.empty_cells(),
+                field.cells()
+            ));
+
+            if !field.samples().is_empty() {
+                let samples = field
+                    .samples()
+                    .iter()
+                    .map(|s| format!("{s:?}"))
+                    .collect::<Vec<_>>();
+                out.push_str(&format!("{indent}samples: {}\n", samples.join(", ")));
+            }
+
+            // Types ruled out by the same cell are listed together.
+            for cell in field
+                .eliminated()
+                .chunk_by(|a, b| a.line == b.line && a.value == b.value)
+            {
+                let types = cell
+                    .iter()
+                    .map(|e| e.parser.type_name(false))
+                    .collect::<Vec<_>>();
+                let line = match cell[0].line {
+                    0 => String::new(),
+                    line => format!(" on line {line}"),
+                };
+                out.push_str(&format!(
+                    "{indent}not {}: {:?}{line}\n",
+                    types.join(", "),
+                    cell[0].value
+                ));
+            }
+        }
+    }
+
+    out
+}
+
+fn json(schema: &Schema) -> String {
+    let columns = schema
+        .tables
+        .iter()
+        .flat_map(|table| {
+            table.fields().into_iter().map(|field| {
+                let eliminated = field
+                    .eliminated()
+                    .iter()
+                    .map(|e| {
+                        json!({
+                            "type": e.parser.type_name(false),
+                            "line": (e.line != 0).then_some(e.line),
+                            "value": e.value,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                json!({
+                    "table": table.name,
+                    "header": field.raw_name,
+                    "name": field.name,
+                    "type": field.type_name(),
+                    "eliminated": eliminated,
+                    "nulls": field.empty_cells(),
+                    "cells": field.cells(),
+                    "samples": field.samples(),
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::to_string_pretty(&columns).expect("Reports can always be serialized.")
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use crate::{infer, Config, ReportFormat};
+
+    #[test]
+    fn table() {
+        let csv = "Id,Score\n1,\n2,300\n-3,4.5\n";
+        let config = Config::default();
+        let schema = infer(config.reader_builder().from_reader(csv.as_bytes()), &config).unwrap();
+
+        assert_eq!(
+            super::report(&schema, ReportFormat::Table),
+            indoc! {r#"
+                Record
+                  "Id"     id: i8
+                           nulls: 0 of 3
+                           samples: "1", "2", "-3"
+                           not u8, u16, u32, u64, u128, char: "-3" on line 4
+                  "Score"  score: Option<f32>
+                           nulls: 1 of 3
+                           samples: "300", "4.5"
+                           not u8, i8, char: "300" on line 3
+                           not u16, u32, u64, u128, i16, i32, i64, i128: "4.5" on line 4
+            "#}
+        );
+    }
+}
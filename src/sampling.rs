@@ -0,0 +1,123 @@
+use std::str::FromStr;
+
+use csv::ByteRecord;
+
+use crate::Error;
+
+/// Which records take part in type inference.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Sampling {
+    /// Every record.
+    #[default]
+    All,
+    /// `N` records picked uniformly at random across the whole input.
+    Random(usize),
+    /// Every `N`th record, starting with the first one.
+    Every(usize),
+}
+
+impl FromStr for Sampling {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, count) = s.split_once(':').unwrap_or((s, ""));
+        let count = || {
+            count
+                .parse::<usize>()
+                .map_err(|_| format!("expected a number of rows, got '{count}'"))
+        };
+
+        match kind {
+            "all" => Ok(Sampling::All),
+            "random" => Ok(Sampling::Random(count()?)),
+            "every" => match count()? {
+                0 => Err("expected a positive step".to_string()),
+                n => Ok(Sampling::Every(n)),
+            },
+            _ => Err(format!(
+                "unknown sampling '{kind}', expected all, random:N or every:N"
+            )),
+        }
+    }
+}
+
+type Records<'a> = Box<dyn Iterator<Item = Result<ByteRecord, Error>> + 'a>;
+
+pub fn sample<'a, I>(records: I, sampling: Sampling) -> Result<Records<'a>, Error>
+where
+    I: Iterator<Item = Result<ByteRecord, Error>> + 'a,
+{
+    match sampling {
+        Sampling::All => Ok(Box::new(records)),
+        Sampling::Random(n) => {
+            let reservoir = reservoir_sample(records, n)?;
+            Ok(Box::new(reservoir.into_iter().map(Ok)))
+        }
+        Sampling::Every(n) => Ok(Box::new(records.step_by(n))),
+    }
+}
+
+/// Algorithm R. Seeded with a constant, so that the generated code is reproducible.
+fn reservoir_sample<T, I>(items: I, n: usize) -> Result<Vec<T>, Error>
+where
+    I: Iterator<Item = Result<T, Error>>,
+{
+    let mut rng = SplitMix64(0x2545_F491_4F6C_DD1D);
+    let mut reservoir = Vec::with_capacity(n);
+
+    for (i, item) in items.enumerate() {
+        let item = item?;
+
+        if i < n {
+            reservoir.push(item);
+        } else {
+            let j = rng.next_below(i as u64 + 1) as usize;
+            if j < n {
+                reservoir[j] = item;
+            }
+        }
+    }
+
+    Ok(reservoir)
+}
+
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn next_below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{reservoir_sample, Sampling};
+
+    #[test]
+    fn parse() {
+        assert_eq!("all".parse(), Ok(Sampling::All));
+        assert_eq!("random:100".parse(), Ok(Sampling::Random(100)));
+        assert_eq!("every:10".parse(), Ok(Sampling::Every(10)));
+        assert!("every:0".parse::<Sampling>().is_err());
+        assert!("random".parse::<Sampling>().is_err());
+        assert!("first:10".parse::<Sampling>().is_err());
+    }
+
+    #[test]
+    fn reservoir() {
+        let sample = reservoir_sample((0..1000).map(Ok), 10).unwrap();
+        assert_eq!(sample.len(), 10);
+        assert!(sample.iter().any(|&i| i >= 10));
+
+        let sample = reservoir_sample((0..5).map(Ok), 10).unwrap();
+        assert_eq!(sample, vec![0, 1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    codegen::Sample,
+    field::{self, Field},
+    Config, Error,
+};
+
+/// Everything inferred from the inputs, ready to be rendered by an
+/// [`Emitter`](crate::Emitter).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Schema {
+    pub tables: Vec<Table>,
+}
+
+impl Schema {
+    /// The schema as JSON, to be loaded again by [`Schema::from_json`].
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Schemas can always be serialized.")
+    }
+
+    /// Loads a schema saved by [`Schema::to_json`]. Only what was inferred is
+    /// saved, so its fields are named and typed according to `config`.
+    pub fn from_json(json: &str, config: &Config) -> Result<Schema, Error> {
+        let mut schema: Schema = serde_json::from_str(json).map_err(Error::CantParseSchema)?;
+
+        for table in &mut schema.tables {
+            for (_, fields) in &mut table.groups {
+                let headers = fields
+                    .iter()
+                    .map(|f| f.raw_name.clone())
+                    .collect::<Vec<_>>();
+                let mut configured = field::for_headers(&headers, config);
+
+                // Captured columns come last, and are text whatever was seen.
+                let columns = fields.iter().filter(|f| !f.is_extra()).count();
+                for (i, (field, inferred)) in configured.iter_mut().zip(fields.iter()).enumerate() {
+                    if inferred.is_extra() {
+                        *field = Field::extra(i - columns + 1, config);
+                    }
+                    field.inherit(inferred);
+                }
+                *fields = configured;
+            }
+        }
+
+        Ok(schema)
+    }
+}
+
+/// The columns inferred from one input.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Table {
+    /// Name of the generated type.
+    pub name: String,
+    /// Fields inferred for each value of the `split_by` column, or for the
+    /// whole input under an empty key.
+    pub groups: Vec<(String, Vec<Field>)>,
+    /// The input's delimiter, where inputs don't all use [`Config::delimiter`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delimiter: Option<u8>,
+    pub(crate) sample: Sample,
+}
+
+impl Table {
+    /// `config`, with the input's delimiter if it has its own.
+    pub fn dialect(&self, config: &Config) -> Config {
+        Config {
+            delimiter: self.delimiter.unwrap_or(config.delimiter),
+            ..config.clone()
+        }
+    }
+}
+
+impl Table {
+    /// The fields of all groups combined, as if the input had not been split.
+    pub fn fields(&self) -> Vec<Field> {
+        let mut groups = self.groups.iter().map(|(_, fields)| fields);
+        let mut merged = groups.next().cloned().unwrap_or_default();
+
+        for fields in groups {
+            for (field, other) in merged.iter_mut().zip(fields) {
+                field.merge(other);
+            }
+        }
+
+        merged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::Schema;
+    use crate::{generate, infer, Config, ExtraColumns};
+
+    #[test]
+    fn round_trip() {
+        let config = Config::builder()
+            .flexible(true)
+            .extra_columns(ExtraColumns::Capture)
+            .with_reader(true)
+            .build();
+        let csv = indoc! {"
+            id,name
+            1,a
+            2,b,x
+        "};
+
+        let reader = config.reader_builder().from_reader(csv.as_bytes());
+        let schema = infer(reader, &config).unwrap();
+        let loaded = Schema::from_json(&schema.to_json(), &config).unwrap();
+
+        let code = generate(&schema, &config).unwrap();
+        assert!(code.contains("pub extra_1: Option<String>,"), "{code}");
+        assert_eq!(generate(&loaded, &config).unwrap(), code);
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::distinct::Distinct;
+
+/// How many sample values to keep for documentation and reports.
+pub(crate) const SAMPLES: usize = 3;
+
+/// Columns with at most this many distinct values, some repeated, are taken
+/// to hold categories.
+const CATEGORIES: usize = 10;
+
+/// Statistics about the values seen for a field, collected on demand.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Stats {
+    pub values: usize,
+    pub nulls: usize,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// Length of the longest value, in characters.
+    #[serde(default)]
+    pub max_len: Option<usize>,
+    pub distinct: Distinct,
+    pub samples: Vec<String>,
+}
+
+impl Stats {
+    pub fn update_for(&mut self, field: &str) {
+        if field.is_empty() {
+            self.nulls += 1;
+            return;
+        }
+
+        self.values += 1;
+
+        let len = field.chars().count();
+        self.max_len = Some(self.max_len.map_or(len, |max| max.max(len)));
+
+        if let Ok(number) = field.parse::<f64>() {
+            self.min = Some(self.min.map_or(number, |min| min.min(number)));
+            self.max = Some(self.max.map_or(number, |max| max.max(number)));
+        }
+
+        if self.distinct.insert(field) && self.samples.len() < SAMPLES {
+            self.samples.push(field.to_string());
+        }
+    }
+
+    pub fn merge(&mut self, other: &Stats) {
+        self.values += other.values;
+        self.nulls += other.nulls;
+        self.min = [self.min, other.min].into_iter().flatten().reduce(f64::min);
+        self.max = [self.max, other.max].into_iter().flatten().reduce(f64::max);
+        self.max_len = self.max_len.max(other.max_len);
+        self.distinct.merge(&other.distinct);
+
+        for sample in &other.samples {
+            if self.samples.len() < SAMPLES && !self.samples.contains(sample) {
+                self.samples.push(sample.clone());
+            }
+        }
+    }
+
+    /// The distinct values seen, if there are few enough of them, and
+    /// repeated, to be categories rather than free-form values.
+    pub fn categories(&self) -> Option<&BTreeSet<String>> {
+        self.distinct
+            .values()
+            .filter(|values| values.len() <= CATEGORIES && values.len() < self.values)
+    }
+
+    pub fn null_percentage(&self) -> f64 {
+        match self.values + self.nulls {
+            0 => 0.0,
+            total => self.nulls as f64 * 100.0 / total as f64,
+        }
+    }
+
+    /// Summary lines for a doc comment. The range is only included for numeric fields.
+    pub fn describe(&self, numeric: bool) -> Vec<String> {
+        let mut lines = vec![];
+
+        if !self.samples.is_empty() {
+            let samples = self
+                .samples
+                .iter()
+                .map(|s| format!("{:?}", truncate(s)))
+                .collect::<Vec<_>>();
+            lines.push(format!("Samples: {}.", samples.join(", ")));
+        }
+
+        if let (true, Some(min), Some(max)) = (numeric, self.min, self.max) {
+            lines.push(format!("Range: {min} to {max}."));
+        }
+
+        if self.distinct.is_estimated() {
+            lines.push(format!("Distinct values: about {}.", self.distinct.len()));
+        } else {
+            lines.push(format!("Distinct values: {}.", self.distinct.len()));
+        }
+        lines.push(format!("Nulls: {:.1}%.", self.null_percentage()));
+
+        lines
+    }
+}
+
+fn truncate(s: &str) -> String {
+    const MAX: usize = 40;
+
+    match s.char_indices().nth(MAX) {
+        Some((i, _)) => format!("{}…", &s[..i]),
+        None => s.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Stats;
+
+    #[test]
+    fn describe() {
+        let mut stats = Stats::default();
+        for value in ["3", "", "10", "3", "7", "-1"] {
+            stats.update_for(value);
+        }
+
+        assert_eq!(
+            stats.describe(true),
+            vec![
+                r#"Samples: "3", "10", "7"."#,
+                "Range: -1 to 10.",
+                "Distinct values: 4.",
+                "Nulls: 16.7%.",
+            ]
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use std::{fmt, sync::Arc};
+
+use proc_macro2::TokenStream;
+
+/// A domain-specific type to infer, like an ISBN or a ticker symbol. A column
+/// gets a custom type when all its values parse as one, in preference to the
+/// built-in types.
+pub trait TypeInfer: Send + Sync {
+    /// Whether a non-empty cell holds a value of this type.
+    fn try_parse(&self, value: &str) -> bool;
+
+    /// The generated type, like `quote! { isbn::Isbn13 }`.
+    fn type_tokens(&self) -> TokenStream;
+
+    /// Of the custom types accepting all values of a column, the one with the
+    /// highest priority is picked, or the first registered on a tie.
+    fn priority(&self) -> u32 {
+        0
+    }
+}
+
+/// The custom types to infer, in registration order.
+#[derive(Clone, Default)]
+pub struct TypeRegistry {
+    types: Vec<Arc<dyn TypeInfer>>,
+}
+
+impl TypeRegistry {
+    pub fn new() -> Self {
+        TypeRegistry::default()
+    }
+
+    pub fn register(&mut self, type_infer: impl TypeInfer + 'static) -> &mut Self {
+        self.types.push(Arc::new(type_infer));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    /// Indices of the types accepting `value`, out of the given ones.
+    pub(crate) fn retain_parsing(&self, indices: &mut Vec<usize>, value: &str) {
+        indices.retain(|&i| self.types.get(i).is_some_and(|t| t.try_parse(value)));
+    }
+
+    /// Name of the type to generate out of the given candidates, if any.
+    pub(crate) fn pick(&self, indices: &[usize]) -> Option<String> {
+        indices
+            .iter()
+            .filter_map(|&i| self.types.get(i))
+            .min_by_key(|t| std::cmp::Reverse(t.priority()))
+            .map(|t| type_name(t.type_tokens()))
+    }
+}
+
+impl fmt::Debug for TypeRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.types.iter().map(|t| type_name(t.type_tokens())))
+            .finish()
+    }
+}
+
+/// Renders a type like Rust code is written, as `a::B<C>` rather than `a :: B < C >`.
+fn type_name(tokens: TokenStream) -> String {
+    tokens
+        .to_string()
+        .replace(" :: ", "::")
+        .replace(":: ", "::")
+        .replace(" < ", "<")
+        .replace("< ", "<")
+        .replace(" <", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use proc_macro2::TokenStream;
+    use quote::quote;
+
+    use super::TypeInfer;
+    use crate::{run_on_str, Config};
+
+    struct Isbn;
+
+    impl TypeInfer for Isbn {
+        fn try_parse(&self, value: &str) -> bool {
+            let digits = value.chars().filter(char::is_ascii_digit).count();
+            value.starts_with("978-") && digits == 13
+        }
+
+        fn type_tokens(&self) -> TokenStream {
+            quote! { isbn::Isbn13 }
+        }
+    }
+
+    #[test]
+    fn custom_type() {
+        let config = Config::builder()
+            .struct_name("Book")
+            .with_imports(false)
+            .register_type(Isbn)
+            .build();
+        let csv = indoc! {"
+            isbn,alternate,title
+            978-3-16-148410-0,,Rust
+            978-0-306-40615-7,978-1-86197-876-9,Serde
+        "};
+
+        assert_eq!(
+            run_on_str(csv, &config).unwrap(),
+            indoc! {"
+                #[derive(Debug, Deserialize)]
+                pub struct Book {
+                    pub isbn: isbn::Isbn13,
+
+                    pub alternate: Option<isbn::Isbn13>,
+
+                    pub title: String,
+                }
+            "}
+        );
+    }
+}
@@ -1,4 +1,10 @@
-#[derive(Copy, Clone, Debug, PartialEq)]
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Config;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum TypeParser {
     U8,
     U16,
@@ -12,16 +18,36 @@ pub enum TypeParser {
     I128,
     F32,
     F64,
+    Duration,
+    Map,
+    Char,
+    Path,
     String,
 }
 
 impl TypeParser {
-    const TYPE_NAMES: [&'static str; 13] = [
-        "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128", "f32", "f64",
+    const TYPE_NAMES: [&'static str; 17] = [
+        "u8",
+        "u16",
+        "u32",
+        "u64",
+        "u128",
+        "i8",
+        "i16",
+        "i32",
+        "i64",
+        "i128",
+        "f32",
+        "f64",
+        "Duration",
+        "HashMap<String, String>",
+        "char",
+        "PathBuf",
         "String",
     ];
 
-    const OPTIONAL_TYPE_NAMES: [&'static str; 13] = [
+    // Maps deserialize empty cells into empty maps, so they are never wrapped.
+    const OPTIONAL_TYPE_NAMES: [&'static str; 17] = [
         "Option<u8>",
         "Option<u16>",
         "Option<u32>",
@@ -34,6 +60,10 @@ impl TypeParser {
         "Option<i128>",
         "Option<f32>",
         "Option<f64>",
+        "Option<Duration>",
+        "HashMap<String, String>",
+        "Option<char>",
+        "Option<PathBuf>",
         "Option<String>",
     ];
 
@@ -51,10 +81,27 @@ impl TypeParser {
             TypeParser::I128,
             TypeParser::F32,
             TypeParser::F64,
+            TypeParser::Duration,
+            TypeParser::Map,
+            TypeParser::Char,
+            TypeParser::Path,
             TypeParser::String,
         ]
     }
 
+    /// Parsers enabled by the given config, in priority order.
+    pub fn enabled(config: &Config) -> Vec<Self> {
+        TypeParser::all()
+            .into_iter()
+            .filter(|p| match p {
+                TypeParser::Duration => config.durations,
+                TypeParser::Map => config.map_cells,
+                TypeParser::Path => config.path_type,
+                _ => true,
+            })
+            .collect()
+    }
+
     fn index(&self) -> usize {
         *self as usize
     }
@@ -68,27 +115,259 @@ impl TypeParser {
     }
 
     pub fn can_parse(&self, field: &str) -> bool {
+        self.accepts(Shape::of(field), field)
+    }
+
+    /// Like [`TypeParser::can_parse`], for a cell already classified, so that
+    /// numeric types are checked without parsing the cell again.
+    pub(crate) fn accepts(&self, shape: Shape, field: &str) -> bool {
+        match self {
+            TypeParser::Duration => parse_duration(field).is_some(),
+            TypeParser::Map => is_map_cell(field),
+            TypeParser::Char => field.chars().count() == 1,
+            TypeParser::Path => is_path(field),
+            _ => self.accepts_shape(shape) == Some(true),
+        }
+    }
+
+    /// Whether a cell of the given shape parses as this type, for the types
+    /// telling cells apart by their shape alone: numbers, and `String`.
+    pub(crate) fn accepts_shape(&self, shape: Shape) -> Option<bool> {
+        let max = match self {
+            TypeParser::U8 => u8::MAX as u128,
+            TypeParser::U16 => u16::MAX as u128,
+            TypeParser::U32 => u32::MAX as u128,
+            TypeParser::U64 => u64::MAX as u128,
+            TypeParser::U128 => u128::MAX,
+            TypeParser::I8 => i8::MAX as u128,
+            TypeParser::I16 => i16::MAX as u128,
+            TypeParser::I32 => i32::MAX as u128,
+            TypeParser::I64 => i64::MAX as u128,
+            TypeParser::I128 => i128::MAX as u128,
+            // Out of range values parse as infinity, so both take the same cells.
+            TypeParser::F32 | TypeParser::F64 => return Some(shape != Shape::Other),
+            TypeParser::String => return Some(true),
+            TypeParser::Duration | TypeParser::Map | TypeParser::Char | TypeParser::Path => {
+                return None
+            }
+        };
+        let signed = self.index() >= TypeParser::I8.index();
+
+        let accepted = match shape {
+            Shape::Integer {
+                negative,
+                magnitude: Some(magnitude),
+            } => {
+                if negative {
+                    // Signed types reach one further below zero than above it.
+                    signed && magnitude <= max + 1
+                } else {
+                    magnitude <= max
+                }
+            }
+            _ => false,
+        };
+        Some(accepted)
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        self.index() <= TypeParser::F64.index()
+    }
+
+    /// Name of the generated helper used to deserialize this type, if any.
+    pub fn deserialize_with(&self, optional: bool) -> Option<&'static str> {
+        match self {
+            TypeParser::Duration if optional => Some("deserialize_optional_duration"),
+            TypeParser::Duration => Some("deserialize_duration"),
+            TypeParser::Map => Some("deserialize_map"),
+            _ => None,
+        }
+    }
+
+    /// Name of the generated helper writing this type back as a cell, if any.
+    pub fn serialize_with(&self, optional: bool) -> Option<&'static str> {
         match self {
-            TypeParser::String => true,
-            TypeParser::U8 => field.parse::<u8>().is_ok(),
-            TypeParser::U16 => field.parse::<u16>().is_ok(),
-            TypeParser::U32 => field.parse::<u32>().is_ok(),
-            TypeParser::U64 => field.parse::<u64>().is_ok(),
-            TypeParser::U128 => field.parse::<u128>().is_ok(),
-            TypeParser::I8 => field.parse::<i8>().is_ok(),
-            TypeParser::I16 => field.parse::<i16>().is_ok(),
-            TypeParser::I32 => field.parse::<i32>().is_ok(),
-            TypeParser::I64 => field.parse::<i64>().is_ok(),
-            TypeParser::I128 => field.parse::<i128>().is_ok(),
-            TypeParser::F32 => field.parse::<f32>().is_ok(),
-            TypeParser::F64 => field.parse::<f64>().is_ok(),
+            TypeParser::Duration if optional => Some("serialize_optional_duration"),
+            TypeParser::Duration => Some("serialize_duration"),
+            TypeParser::Map => Some("serialize_map"),
+            _ => None,
         }
     }
 }
 
+/// What a cell looks like to the numeric types, found in a single pass over
+/// its bytes, following the syntax of Rust's `FromStr` for numbers.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Shape {
+    /// An optional sign followed by ASCII digits, with the magnitude unless
+    /// it's beyond `u128`.
+    Integer {
+        negative: bool,
+        magnitude: Option<u128>,
+    },
+    /// Any other number floats parse: decimals, exponents, `inf` and `NaN`.
+    Float,
+    Other,
+}
+
+impl Shape {
+    pub fn of(field: &str) -> Self {
+        Shape::of_bytes(field.as_bytes())
+    }
+
+    /// Like [`Shape::of`], for a cell not yet known to be UTF-8. Numbers are
+    /// ASCII, so any other byte makes it [`Shape::Other`].
+    pub fn of_bytes(field: &[u8]) -> Self {
+        let (negative, rest) = match field {
+            [b'-', rest @ ..] => (true, rest),
+            [b'+', rest @ ..] => (false, rest),
+            rest => (false, rest),
+        };
+
+        let mut magnitude = Some(0u128);
+        let mut digits = 0;
+        for &b in rest {
+            if !b.is_ascii_digit() {
+                break;
+            }
+            magnitude = magnitude
+                .and_then(|m| m.checked_mul(10))
+                .and_then(|m| m.checked_add((b - b'0') as u128));
+            digits += 1;
+        }
+
+        if digits > 0 && digits == rest.len() {
+            return Shape::Integer {
+                negative,
+                magnitude,
+            };
+        }
+
+        if is_float(rest, digits) {
+            Shape::Float
+        } else {
+            Shape::Other
+        }
+    }
+}
+
+/// Whether an unsigned number is a float, given how many digits it starts with:
+/// `1.`, `.5`, `1.5e-3`, `inf`, `infinity` or `NaN`, in any case.
+fn is_float(number: &[u8], int_digits: usize) -> bool {
+    let words: [&[u8]; 3] = [b"inf", b"infinity", b"nan"];
+    if words.iter().any(|w| number.eq_ignore_ascii_case(w)) {
+        return true;
+    }
+
+    let rest = &number[int_digits..];
+    let (frac_digits, rest) = match rest {
+        [b'.', rest @ ..] => {
+            let digits = rest.iter().take_while(|b| b.is_ascii_digit()).count();
+            (digits, &rest[digits..])
+        }
+        rest => (0, rest),
+    };
+    if int_digits + frac_digits == 0 {
+        return false;
+    }
+
+    match rest {
+        [] => true,
+        [b'e' | b'E', exponent @ ..] => {
+            let exponent = exponent
+                .strip_prefix(b"-")
+                .or_else(|| exponent.strip_prefix(b"+"))
+                .unwrap_or(exponent);
+            !exponent.is_empty() && exponent.iter().all(u8::is_ascii_digit)
+        }
+        _ => false,
+    }
+}
+
+/// Parses either `HH:MM:SS`/`MM:SS` or unit strings like `1h30m`, `90s` and `250ms`.
+/// Keep in sync with the helper generated in `codegen::helpers`.
+pub fn parse_duration(field: &str) -> Option<Duration> {
+    if field.contains(':') {
+        let parts = field.split(':').collect::<Vec<_>>();
+        if !(2..=3).contains(&parts.len()) {
+            return None;
+        }
+
+        let mut secs: u64 = 0;
+        for (i, part) in parts.iter().enumerate() {
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            let value = part.parse::<u64>().ok()?;
+            // Only the leading part may run past a minute or an hour.
+            if i > 0 && value > 59 {
+                return None;
+            }
+            secs = secs.checked_mul(60)?.checked_add(value)?;
+        }
+
+        return Some(Duration::from_secs(secs));
+    }
+
+    if field.is_empty() {
+        return None;
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = field;
+
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        if digits == 0 {
+            return None;
+        }
+
+        let (value, tail) = rest.split_at(digits);
+        let value = value.parse::<u64>().ok()?;
+
+        let unit_len = tail
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+
+        let part = match unit {
+            "d" => Duration::from_secs(value.checked_mul(86400)?),
+            "h" => Duration::from_secs(value.checked_mul(3600)?),
+            "m" => Duration::from_secs(value.checked_mul(60)?),
+            "s" => Duration::from_secs(value),
+            "ms" => Duration::from_millis(value),
+            _ => return None,
+        };
+        total = total.checked_add(part)?;
+        rest = tail;
+    }
+
+    Some(total)
+}
+
+/// Matches values containing a path separator, excluding URLs and purely numeric
+/// values such as `2024/01/02` or `1/2`.
+fn is_path(field: &str) -> bool {
+    field.contains(['/', '\\'])
+        && !field.contains("://")
+        && !field.contains(['\n', '\r'])
+        && field.contains(|c: char| c.is_alphabetic() || c == '.')
+}
+
+/// Matches cells of the form `k1=v1;k2=v2`, allowing a trailing separator.
+fn is_map_cell(field: &str) -> bool {
+    let mut pairs = field.strip_suffix(';').unwrap_or(field).split(';');
+
+    pairs.all(|pair| {
+        pair.split_once('=')
+            .is_some_and(|(key, _)| !key.trim().is_empty())
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use super::TypeParser;
+    use super::{parse_duration, Shape, TypeParser};
+    use std::time::Duration;
 
     #[test]
     fn names() {
@@ -113,8 +392,152 @@ mod tests {
                 ("i128", "Option<i128>"),
                 ("f32", "Option<f32>"),
                 ("f64", "Option<f64>"),
+                ("Duration", "Option<Duration>"),
+                ("HashMap<String, String>", "HashMap<String, String>"),
+                ("char", "Option<char>"),
+                ("PathBuf", "Option<PathBuf>"),
                 ("String", "Option<String>"),
             ]
         );
     }
+
+    #[test]
+    fn numbers() {
+        let cells = [
+            "0",
+            "-0",
+            "+7",
+            "255",
+            "256",
+            "-128",
+            "-129",
+            "007",
+            "-",
+            "+",
+            "",
+            " 1",
+            "1 ",
+            "340282366920938463463374607431768211455",
+            "340282366920938463463374607431768211456",
+            "-170141183460469231731687303715884105728",
+            "-170141183460469231731687303715884105729",
+            "1.0",
+            "-1.",
+            ".5",
+            "-.5",
+            ".",
+            "-.",
+            "1e3",
+            "1E-3",
+            "1.5e+10",
+            "1e",
+            "1e+",
+            "e5",
+            ".e5",
+            "1.2.3",
+            "1_000",
+            "0x10",
+            "inf",
+            "-Infinity",
+            "NaN",
+            "nan1",
+            "infinit",
+            "١٢",
+            "1,5",
+        ];
+
+        for cell in cells {
+            let expected = [
+                cell.parse::<u8>().is_ok(),
+                cell.parse::<u16>().is_ok(),
+                cell.parse::<u32>().is_ok(),
+                cell.parse::<u64>().is_ok(),
+                cell.parse::<u128>().is_ok(),
+                cell.parse::<i8>().is_ok(),
+                cell.parse::<i16>().is_ok(),
+                cell.parse::<i32>().is_ok(),
+                cell.parse::<i64>().is_ok(),
+                cell.parse::<i128>().is_ok(),
+                cell.parse::<f32>().is_ok(),
+                cell.parse::<f64>().is_ok(),
+            ];
+            let found = TypeParser::all()
+                .iter()
+                .take(expected.len())
+                .map(|p| p.can_parse(cell))
+                .collect::<Vec<_>>();
+
+            assert_eq!(found, expected, "{cell:?}");
+        }
+    }
+
+    #[test]
+    fn shapes() {
+        let integer = |negative, magnitude| Shape::Integer {
+            negative,
+            magnitude,
+        };
+
+        assert_eq!(Shape::of("42"), integer(false, Some(42)));
+        assert_eq!(Shape::of("+007"), integer(false, Some(7)));
+        assert_eq!(Shape::of("-0"), integer(true, Some(0)));
+        assert_eq!(
+            Shape::of("340282366920938463463374607431768211455"),
+            integer(false, Some(u128::MAX))
+        );
+        assert_eq!(
+            Shape::of("-340282366920938463463374607431768211456"),
+            integer(true, None)
+        );
+        assert_eq!(Shape::of("1.5e-3"), Shape::Float);
+        assert_eq!(Shape::of("-.5"), Shape::Float);
+        assert_eq!(Shape::of("INF"), Shape::Float);
+        assert_eq!(Shape::of("1e"), Shape::Other);
+        assert_eq!(Shape::of("--1"), Shape::Other);
+        assert_eq!(Shape::of(""), Shape::Other);
+
+        assert_eq!(Shape::of_bytes(b"12"), integer(false, Some(12)));
+        assert_eq!(Shape::of_bytes(b"1\xff"), Shape::Other);
+        assert_eq!(Shape::of_bytes("١٢".as_bytes()), Shape::Other);
+    }
+
+    #[test]
+    fn map_cells() {
+        assert!(TypeParser::Map.can_parse("a=1;b=2"));
+        assert!(TypeParser::Map.can_parse("a=1;"));
+        assert!(TypeParser::Map.can_parse("a="));
+        assert!(!TypeParser::Map.can_parse("a=1;b"));
+        assert!(!TypeParser::Map.can_parse("=1"));
+        assert!(!TypeParser::Map.can_parse("plain text"));
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("90s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("1h30m"), Some(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("250ms"), Some(Duration::from_millis(250)));
+        assert_eq!(parse_duration("00:05:12"), Some(Duration::from_secs(312)));
+        assert_eq!(parse_duration("05:12"), Some(Duration::from_secs(312)));
+        assert_eq!(parse_duration("90"), None);
+        assert_eq!(parse_duration("1x"), None);
+        assert_eq!(parse_duration("1:2:3:4"), None);
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("05:60"), None);
+        assert_eq!(parse_duration("18446744073709551615:1"), None);
+        assert_eq!(
+            parse_duration("18446744073709551615s18446744073709551615s"),
+            None
+        );
+    }
+
+    #[test]
+    fn paths() {
+        assert!(TypeParser::Path.can_parse("/usr/share/icons/app.png"));
+        assert!(TypeParser::Path.can_parse("assets/textures/grass.dds"));
+        assert!(TypeParser::Path.can_parse("C:\\Games\\save.dat"));
+        assert!(TypeParser::Path.can_parse("./run.sh"));
+        assert!(!TypeParser::Path.can_parse("https://example.com/a.png"));
+        assert!(!TypeParser::Path.can_parse("2024/01/02"));
+        assert!(!TypeParser::Path.can_parse("grass.dds"));
+    }
 }
@@ -0,0 +1,65 @@
+//! Bindings for JavaScript, to generate code for CSV pasted into a web page.
+
+use clap::ValueEnum;
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+use crate::{run_on_str, Config, Emit, FieldCase};
+
+/// Options of [`infer_from_str`], as an object like
+/// `{ structName: "Order", delimiter: ";" }`. All of them can be left out.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct Options {
+    struct_name: Option<String>,
+    delimiter: Option<char>,
+    has_headers: Option<bool>,
+    with_imports: Option<bool>,
+    emit: Option<String>,
+    field_case: Option<String>,
+}
+
+impl TryFrom<Options> for Config {
+    type Error = String;
+
+    fn try_from(options: Options) -> Result<Self, Self::Error> {
+        let mut builder = Config::builder();
+
+        if let Some(name) = options.struct_name {
+            builder = builder.struct_name(name);
+        }
+        if let Some(delimiter) = options.delimiter {
+            let delimiter = u8::try_from(delimiter)
+                .map_err(|_| format!("delimiter '{delimiter}' is not a single byte"))?;
+            builder = builder.delimiter(delimiter);
+        }
+        if let Some(has_headers) = options.has_headers {
+            builder = builder.has_headers(has_headers);
+        }
+        if let Some(with_imports) = options.with_imports {
+            builder = builder.with_imports(with_imports);
+        }
+        if let Some(emit) = options.emit {
+            builder = builder.emit(Emit::from_str(&emit, true)?);
+        }
+        if let Some(field_case) = options.field_case {
+            builder = builder.field_case(FieldCase::from_str(&field_case, true)?);
+        }
+
+        Ok(builder.build())
+    }
+}
+
+/// Generates code for the CSV in `csv`, with the options in `opts`, which
+/// may be `undefined`.
+#[wasm_bindgen(js_name = inferFromStr)]
+pub fn infer_from_str(csv: &str, opts: JsValue) -> Result<String, JsError> {
+    let options = if opts.is_undefined() || opts.is_null() {
+        Options::default()
+    } else {
+        serde_wasm_bindgen::from_value(opts).map_err(|e| JsError::new(&e.to_string()))?
+    };
+    let config = Config::try_from(options).map_err(|e| JsError::new(&e))?;
+
+    run_on_str(csv, &config).map_err(|e| JsError::new(&e.to_string()))
+}
@@ -0,0 +1,392 @@
+//! Generated code checked in under `generated/`, compiled here to make sure
+//! it reads the input it was generated from.
+
+use csv2serde::{generate, infer_all, run_on_str, Config, ExtraColumns};
+
+const TRANSACTIONS: &str = "\
+id,type,amount,note
+1,sale,9.5,
+2,refund,3,late
+3,sale,12,gift
+";
+
+const PLACES: &str = "\
+id,addr_floor,addr_city,addr_lat
+1,3,Boston,1.5
+2,,NYC,
+";
+
+const EXTRAS: &str = "\
+id,name
+1,a
+2,b,x,y
+3,c,z
+";
+
+const JOBS: &str = "\
+id,tags,wait,pause
+1,a=1;b=2,1h30m,
+2,,90s,2m
+";
+
+const SCORES: &str = "\
+id,name,score
+1,Ann,5
+2,,
+";
+
+const KEYWORDS: &str = "\
+self,Self,crate,super,type
+1,a,b,c,d
+";
+
+const REPEATS: &str = "\
+id,amount,amount
+1,2,3
+2,4,
+";
+
+const CODES: &str = "\
+COL_1,COL_2
+1,x
+2,y
+";
+
+const CUSTOMERS: &str = "\
+id,addr_city,addr_zip
+1,Boston,2134
+";
+
+const SUPPLIERS: &str = "\
+name,addr_city,addr_zip
+Acme,NYC,10001
+";
+
+mod split_by {
+    include!("generated/split_by.rs");
+}
+use split_by::Transaction;
+
+mod nest_prefixes {
+    include!("generated/nest_prefixes.rs");
+}
+
+mod extra_columns {
+    include!("generated/extra_columns.rs");
+}
+
+mod with_writer {
+    include!("generated/with_writer.rs");
+}
+
+mod serde_default {
+    include!("generated/serde_default.rs");
+}
+
+mod keywords {
+    include!("generated/keywords.rs");
+}
+
+mod repeated_headers {
+    include!("generated/repeated_headers.rs");
+}
+
+mod headers {
+    include!("generated/headers.rs");
+}
+
+mod multiple_inputs {
+    include!("generated/multiple_inputs.rs");
+}
+
+#[test]
+fn split_by() {
+    let config = Config::builder()
+        .split_by("type".to_string())
+        .struct_name("Transaction")
+        .with_reader(true)
+        .build();
+    assert_eq!(
+        run_on_str(TRANSACTIONS, &config).unwrap(),
+        include_str!("generated/split_by.rs")
+    );
+
+    let path = std::env::temp_dir().join("csv2serde_split_by.csv");
+    std::fs::write(&path, TRANSACTIONS).unwrap();
+    let rows = split_by::read_transactions(&path).unwrap();
+
+    let [Transaction::Sale(first), Transaction::Refund(second), Transaction::Sale(third)] =
+        &rows[..]
+    else {
+        panic!("unexpected variants: {rows:?}");
+    };
+    assert_eq!(
+        (first.id, first.amount, first.note.as_deref()),
+        (1, 9.5, None)
+    );
+    assert_eq!(
+        (second.id, second.amount, second.note.as_str()),
+        (2, 3, "late")
+    );
+    assert_eq!(
+        (third.id, third.amount, third.note.as_deref()),
+        (3, 12.0, Some("gift"))
+    );
+}
+
+#[test]
+fn nest_prefixes() {
+    let config = Config::builder()
+        .nest_separator("_".to_string())
+        .struct_name("Place")
+        .with_reader(true)
+        .build();
+    assert_eq!(
+        run_on_str(PLACES, &config).unwrap(),
+        include_str!("generated/nest_prefixes.rs")
+    );
+
+    let path = std::env::temp_dir().join("csv2serde_nest_prefixes.csv");
+    std::fs::write(&path, PLACES).unwrap();
+    let rows = nest_prefixes::read_places(&path).unwrap();
+
+    let [first, second] = &rows[..] else {
+        panic!("unexpected rows: {rows:?}");
+    };
+    assert_eq!(
+        (
+            first.id,
+            first.addr.floor,
+            first.addr.city.as_str(),
+            first.addr.lat
+        ),
+        (1, Some(3), "Boston", Some(1.5))
+    );
+    assert_eq!(
+        (
+            second.id,
+            second.addr.floor,
+            second.addr.city.as_str(),
+            second.addr.lat
+        ),
+        (2, None, "NYC", None)
+    );
+}
+
+#[test]
+fn extra_columns() {
+    let config = Config::builder()
+        .flexible(true)
+        .extra_columns(ExtraColumns::Capture)
+        .struct_name("Extra")
+        .with_reader(true)
+        .build();
+    assert_eq!(
+        run_on_str(EXTRAS, &config).unwrap(),
+        include_str!("generated/extra_columns.rs")
+    );
+
+    let path = std::env::temp_dir().join("csv2serde_extra_columns.csv");
+    std::fs::write(&path, EXTRAS).unwrap();
+    let rows = extra_columns::read_extras(&path).unwrap();
+
+    let captured = rows
+        .iter()
+        .map(|row| {
+            (
+                row.id,
+                row.name,
+                row.extra_1.as_deref(),
+                row.extra_2.as_deref(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        captured,
+        [
+            (1, 'a', None, None),
+            (2, 'b', Some("x"), Some("y")),
+            (3, 'c', Some("z"), None),
+        ]
+    );
+}
+
+#[test]
+fn with_writer() {
+    let config = Config::builder()
+        .map_cells(true)
+        .durations(true)
+        .struct_name("Job")
+        .with_reader(true)
+        .with_writer(true)
+        .build();
+    assert_eq!(
+        run_on_str(JOBS, &config).unwrap(),
+        include_str!("generated/with_writer.rs")
+    );
+
+    let path = std::env::temp_dir().join("csv2serde_with_writer.csv");
+    std::fs::write(&path, JOBS).unwrap();
+    let rows = with_writer::read_jobs(&path).unwrap();
+    with_writer::write_jobs(&path, &rows).unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "id,tags,wait,pause\n1,a=1;b=2,1h30m,\n2,,1m30s,2m\n"
+    );
+    let read_back = with_writer::read_jobs(&path).unwrap();
+    assert_eq!(
+        (
+            read_back[0].id,
+            &read_back[0].tags,
+            read_back[1].wait,
+            read_back[1].pause
+        ),
+        (1, &rows[0].tags, rows[1].wait, rows[1].pause)
+    );
+}
+
+#[test]
+fn serde_default() {
+    let config = Config::builder()
+        .struct_name("Score")
+        .serde_default(true)
+        .skip_serializing_none(true)
+        .with_reader(true)
+        .with_writer(true)
+        .build();
+    assert_eq!(
+        run_on_str(SCORES, &config).unwrap(),
+        include_str!("generated/serde_default.rs")
+    );
+
+    // `None` fields are still written, to keep every row's cells in line.
+    let path = std::env::temp_dir().join("csv2serde_serde_default.csv");
+    std::fs::write(&path, SCORES).unwrap();
+    let rows = serde_default::read_scores(&path).unwrap();
+    serde_default::write_scores(&path, &rows).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), SCORES);
+
+    std::fs::write(&path, "id\n3\n").unwrap();
+    let rows = serde_default::read_scores(&path).unwrap();
+    assert_eq!(
+        (rows[0].id, rows[0].name.as_deref(), rows[0].score),
+        (3, None, None)
+    );
+}
+
+#[test]
+fn keywords() {
+    let config = Config::builder()
+        .struct_name("Keyword")
+        .with_reader(true)
+        .build();
+    assert_eq!(
+        run_on_str(KEYWORDS, &config).unwrap(),
+        include_str!("generated/keywords.rs")
+    );
+
+    let path = std::env::temp_dir().join("csv2serde_keywords.csv");
+    std::fs::write(&path, KEYWORDS).unwrap();
+    let rows = keywords::read_keywords(&path).unwrap();
+    let row = &rows[0];
+    assert_eq!(
+        (
+            row.self_field,
+            row.self_field_2,
+            row.crate_field,
+            row.super_field,
+            row.r#type
+        ),
+        (1, 'a', 'b', 'c', 'd')
+    );
+}
+
+#[test]
+fn repeated_headers() {
+    let config = Config::builder()
+        .struct_name("Rep")
+        .with_reader(true)
+        .with_iter(true)
+        .build();
+    assert_eq!(
+        run_on_str(REPEATS, &config).unwrap(),
+        include_str!("generated/repeated_headers.rs")
+    );
+
+    let path = std::env::temp_dir().join("csv2serde_repeated_headers.csv");
+    std::fs::write(&path, REPEATS).unwrap();
+    let rows = repeated_headers::read_reps(&path).unwrap();
+    let iterated = repeated_headers::iter_reps(&path)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    for rows in [rows, iterated] {
+        let amounts = rows
+            .iter()
+            .map(|row| (row.id, row.amount, row.amount_2))
+            .collect::<Vec<_>>();
+        assert_eq!(amounts, [(1, 2, Some(3)), (2, 4, None)]);
+    }
+}
+
+#[test]
+fn headers() {
+    let config = Config::builder()
+        .headers(vec!["id".to_string(), "name".to_string()])
+        .struct_name("Item")
+        .with_reader(true)
+        .build();
+    assert_eq!(
+        run_on_str(CODES, &config).unwrap(),
+        include_str!("generated/headers.rs")
+    );
+
+    let path = std::env::temp_dir().join("csv2serde_headers.csv");
+    std::fs::write(&path, CODES).unwrap();
+    let rows = headers::read_items(&path).unwrap();
+
+    let items = rows
+        .iter()
+        .map(|row| (row.id, row.name))
+        .collect::<Vec<_>>();
+    assert_eq!(items, [(1, 'x'), (2, 'y')]);
+}
+
+#[test]
+fn multiple_inputs() {
+    let config = Config::builder()
+        .nest_separator("_".to_string())
+        .with_reader(true)
+        .build();
+    let inputs = [("Customer", CUSTOMERS), ("Supplier", SUPPLIERS)].map(|(name, csv)| {
+        let reader = config.reader_builder().from_reader(csv.as_bytes());
+        (name.to_string(), reader)
+    });
+    let schema = infer_all(inputs, &config).unwrap();
+    assert_eq!(
+        generate(&schema, &config).unwrap(),
+        include_str!("generated/multiple_inputs.rs")
+    );
+
+    let customers = std::env::temp_dir().join("csv2serde_customers.csv");
+    let suppliers = std::env::temp_dir().join("csv2serde_suppliers.csv");
+    std::fs::write(&customers, CUSTOMERS).unwrap();
+    std::fs::write(&suppliers, SUPPLIERS).unwrap();
+    let customer = &multiple_inputs::read_customers(&customers).unwrap()[0];
+    let supplier = &multiple_inputs::read_suppliers(&suppliers).unwrap()[0];
+
+    assert_eq!(
+        (customer.id, customer.addr.city.as_str(), customer.addr.zip),
+        (1, "Boston", 2134)
+    );
+    assert_eq!(
+        (
+            supplier.name.as_str(),
+            supplier.addr.city.as_str(),
+            supplier.addr.zip
+        ),
+        ("Acme", "NYC", 10001)
+    );
+}
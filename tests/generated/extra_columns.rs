@@ -0,0 +1,24 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Extra {
+    pub id: u8,
+
+    pub name: char,
+
+    pub extra_1: Option<String>,
+
+    pub extra_2: Option<String>,
+}
+
+pub fn read_extras(path: impl AsRef<std::path::Path>) -> Result<Vec<Extra>, csv::Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(b',')
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_path(path)?;
+    let mut headers = reader.headers()?.clone();
+    headers.extend(["extra_1", "extra_2"]);
+    reader.set_headers(headers);
+    reader.deserialize().collect()
+}
@@ -0,0 +1,18 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Item {
+    pub id: u8,
+
+    pub name: char,
+}
+
+pub fn read_items(path: impl AsRef<std::path::Path>) -> Result<Vec<Item>, csv::Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(b',')
+        .trim(csv::Trim::All)
+        .from_path(path)?;
+    reader.headers()?;
+    reader.set_headers(csv::StringRecord::from(vec!["id", "name"]));
+    reader.deserialize().collect()
+}
@@ -0,0 +1,29 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Keyword {
+    #[serde(rename = "self")]
+    pub self_field: u8,
+
+    #[serde(rename = "Self")]
+    pub self_field_2: char,
+
+    #[serde(rename = "crate")]
+    pub crate_field: char,
+
+    #[serde(rename = "super")]
+    pub super_field: char,
+
+    pub r#type: char,
+}
+
+pub fn read_keywords(
+    path: impl AsRef<std::path::Path>,
+) -> Result<Vec<Keyword>, csv::Error> {
+    csv::ReaderBuilder::new()
+        .delimiter(b',')
+        .trim(csv::Trim::All)
+        .from_path(path)?
+        .deserialize()
+        .collect()
+}
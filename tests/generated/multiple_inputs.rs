@@ -0,0 +1,57 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Customer {
+    pub id: u8,
+
+    #[serde(flatten)]
+    pub addr: CustomerAddr,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CustomerAddr {
+    #[serde(rename = "addr_city")]
+    pub city: String,
+
+    #[serde(rename = "addr_zip")]
+    pub zip: u16,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Supplier {
+    pub name: String,
+
+    #[serde(flatten)]
+    pub addr: SupplierAddr,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SupplierAddr {
+    #[serde(rename = "addr_city")]
+    pub city: String,
+
+    #[serde(rename = "addr_zip")]
+    pub zip: u16,
+}
+
+pub fn read_customers(
+    path: impl AsRef<std::path::Path>,
+) -> Result<Vec<Customer>, csv::Error> {
+    csv::ReaderBuilder::new()
+        .delimiter(b',')
+        .trim(csv::Trim::All)
+        .from_path(path)?
+        .deserialize()
+        .collect()
+}
+
+pub fn read_suppliers(
+    path: impl AsRef<std::path::Path>,
+) -> Result<Vec<Supplier>, csv::Error> {
+    csv::ReaderBuilder::new()
+        .delimiter(b',')
+        .trim(csv::Trim::All)
+        .from_path(path)?
+        .deserialize()
+        .collect()
+}
@@ -0,0 +1,72 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Place {
+    pub id: u8,
+
+    #[serde(flatten)]
+    pub addr: PlaceAddr,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlaceAddr {
+    #[serde(rename = "addr_floor")]
+    #[serde(deserialize_with = "deserialize_flattened_option")]
+    pub floor: Option<u8>,
+
+    #[serde(rename = "addr_city")]
+    pub city: String,
+
+    #[serde(rename = "addr_lat")]
+    #[serde(deserialize_with = "deserialize_flattened_option")]
+    pub lat: Option<f32>,
+}
+
+pub fn read_places(path: impl AsRef<std::path::Path>) -> Result<Vec<Place>, csv::Error> {
+    csv::ReaderBuilder::new()
+        .delimiter(b',')
+        .trim(csv::Trim::All)
+        .from_path(path)?
+        .deserialize()
+        .collect()
+}
+
+fn deserialize_flattened_option<'de, D, T>(
+    deserializer: D,
+) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    struct Cell;
+    impl serde::de::Visitor<'_> for Cell {
+        type Value = String;
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a cell")
+        }
+        fn visit_str<E>(self, v: &str) -> Result<String, E> {
+            Ok(v.to_string())
+        }
+        fn visit_bool<E>(self, v: bool) -> Result<String, E> {
+            Ok(v.to_string())
+        }
+        fn visit_i64<E>(self, v: i64) -> Result<String, E> {
+            Ok(v.to_string())
+        }
+        fn visit_u64<E>(self, v: u64) -> Result<String, E> {
+            Ok(v.to_string())
+        }
+        fn visit_f64<E>(self, v: f64) -> Result<String, E> {
+            Ok(v.to_string())
+        }
+        fn visit_unit<E>(self) -> Result<String, E> {
+            Ok(String::new())
+        }
+    }
+    let cell = deserializer.deserialize_any(Cell)?;
+    if cell.is_empty() {
+        return Ok(None);
+    }
+    cell.parse().map(Some).map_err(serde::de::Error::custom)
+}
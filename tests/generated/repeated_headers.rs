@@ -0,0 +1,31 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Rep {
+    pub id: u8,
+
+    pub amount: u8,
+
+    /// Column `amount`.
+    pub amount_2: Option<u8>,
+}
+
+pub fn read_reps(path: impl AsRef<std::path::Path>) -> Result<Vec<Rep>, csv::Error> {
+    csv::ReaderBuilder::new()
+        .delimiter(b',')
+        .trim(csv::Trim::All)
+        .from_path(path)?
+        .records()
+        .map(|record| record?.deserialize(None))
+        .collect()
+}
+
+pub fn iter_reps(
+    path: impl AsRef<std::path::Path>,
+) -> Result<impl Iterator<Item = Result<Rep, csv::Error>>, csv::Error> {
+    let reader = csv::ReaderBuilder::new()
+        .delimiter(b',')
+        .trim(csv::Trim::All)
+        .from_path(path)?;
+    Ok(reader.into_records().map(|record| record?.deserialize(None)))
+}
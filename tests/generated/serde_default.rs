@@ -0,0 +1,34 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Score {
+    pub id: u8,
+
+    #[serde(default)]
+    pub name: Option<String>,
+
+    #[serde(default)]
+    pub score: Option<u8>,
+}
+
+pub fn read_scores(path: impl AsRef<std::path::Path>) -> Result<Vec<Score>, csv::Error> {
+    csv::ReaderBuilder::new()
+        .delimiter(b',')
+        .trim(csv::Trim::All)
+        .from_path(path)?
+        .deserialize()
+        .collect()
+}
+
+pub fn write_scores(
+    path: impl AsRef<std::path::Path>,
+    rows: &[Score],
+) -> Result<(), csv::Error> {
+    let mut writer = csv::WriterBuilder::new().delimiter(b',').from_path(path)?;
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use serde::Deserialize;
+
+#[derive(Debug)]
+pub enum Transaction {
+    Sale(Sale),
+    Refund(Refund),
+}
+
+impl<'de> Deserialize<'de> for Transaction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+        use std::collections::HashMap;
+        let row: HashMap<String, String> = Deserialize::deserialize(deserializer)?;
+        let tag = row.get("type").ok_or_else(|| D::Error::missing_field("type"))?;
+        let headers = row.keys().collect::<csv::StringRecord>();
+        let values = row.values().collect::<csv::StringRecord>();
+        let variant = match tag.as_str() {
+            "sale" => values.deserialize(Some(&headers)).map(Transaction::Sale),
+            "refund" => values.deserialize(Some(&headers)).map(Transaction::Refund),
+            other => return Err(D::Error::unknown_variant(other, &["sale", "refund"])),
+        };
+        variant.map_err(D::Error::custom)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Sale {
+    pub id: u8,
+
+    pub amount: f32,
+
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Refund {
+    pub id: u8,
+
+    pub amount: u8,
+
+    pub note: String,
+}
+
+pub fn read_transactions(
+    path: impl AsRef<std::path::Path>,
+) -> Result<Vec<Transaction>, csv::Error> {
+    csv::ReaderBuilder::new()
+        .delimiter(b',')
+        .trim(csv::Trim::All)
+        .from_path(path)?
+        .deserialize()
+        .collect()
+}
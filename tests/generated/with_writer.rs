@@ -0,0 +1,252 @@
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Job {
+    pub id: u8,
+
+    #[serde(deserialize_with = "deserialize_map")]
+    #[serde(serialize_with = "serialize_map")]
+    pub tags: HashMap<String, String>,
+
+    #[serde(deserialize_with = "deserialize_duration")]
+    #[serde(serialize_with = "serialize_duration")]
+    pub wait: Duration,
+
+    #[serde(deserialize_with = "deserialize_optional_duration")]
+    #[serde(serialize_with = "serialize_optional_duration")]
+    pub pause: Option<Duration>,
+}
+
+pub fn read_jobs(path: impl AsRef<std::path::Path>) -> Result<Vec<Job>, csv::Error> {
+    csv::ReaderBuilder::new()
+        .delimiter(b',')
+        .trim(csv::Trim::All)
+        .from_path(path)?
+        .deserialize()
+        .collect()
+}
+
+pub fn write_jobs(
+    path: impl AsRef<std::path::Path>,
+    rows: &[Job],
+) -> Result<(), csv::Error> {
+    let mut writer = csv::WriterBuilder::new().delimiter(b',').from_path(path)?;
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn deserialize_duration<'de, D>(deserializer: D) -> Result<std::time::Duration, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    fn parse(cell: &str) -> Option<std::time::Duration> {
+        use std::time::Duration;
+        if cell.contains(':') {
+            let parts = cell.split(':').collect::<Vec<_>>();
+            if !(2..=3).contains(&parts.len()) {
+                return None;
+            }
+            let mut secs: u64 = 0;
+            for (i, part) in parts.iter().enumerate() {
+                if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                    return None;
+                }
+                let value = part.parse::<u64>().ok()?;
+                if i > 0 && value > 59 {
+                    return None;
+                }
+                secs = secs.checked_mul(60)?.checked_add(value)?;
+            }
+            return Some(Duration::from_secs(secs));
+        }
+        if cell.is_empty() {
+            return None;
+        }
+        let mut total = Duration::ZERO;
+        let mut rest = cell;
+        while !rest.is_empty() {
+            let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+            if digits == 0 {
+                return None;
+            }
+            let (value, tail) = rest.split_at(digits);
+            let value = value.parse::<u64>().ok()?;
+            let unit_len = tail.find(|c: char| c.is_ascii_digit()).unwrap_or(tail.len());
+            let (unit, tail) = tail.split_at(unit_len);
+            let part = match unit {
+                "d" => Duration::from_secs(value.checked_mul(86400)?),
+                "h" => Duration::from_secs(value.checked_mul(3600)?),
+                "m" => Duration::from_secs(value.checked_mul(60)?),
+                "s" => Duration::from_secs(value),
+                "ms" => Duration::from_millis(value),
+                _ => return None,
+            };
+            total = total.checked_add(part)?;
+            rest = tail;
+        }
+        Some(total)
+    }
+    let cell = <String as serde::Deserialize>::deserialize(deserializer)?;
+    parse(&cell)
+        .ok_or_else(|| { serde::de::Error::custom(format!("invalid duration: {cell}")) })
+}
+
+fn deserialize_map<'de, D>(
+    deserializer: D,
+) -> Result<std::collections::HashMap<String, String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let cell = <Option<String> as serde::Deserialize>::deserialize(deserializer)?;
+    let cell = cell.unwrap_or_default();
+    let cell = cell.strip_suffix(';').unwrap_or(&cell);
+    if cell.is_empty() {
+        return Ok(Default::default());
+    }
+    cell.split(';')
+        .map(|pair| {
+            pair.split_once('=')
+                .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+                .ok_or_else(|| {
+                    serde::de::Error::custom(format!("invalid key=value pair: {pair}"))
+                })
+        })
+        .collect()
+}
+
+fn deserialize_optional_duration<'de, D>(
+    deserializer: D,
+) -> Result<Option<std::time::Duration>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    fn parse(cell: &str) -> Option<std::time::Duration> {
+        use std::time::Duration;
+        if cell.contains(':') {
+            let parts = cell.split(':').collect::<Vec<_>>();
+            if !(2..=3).contains(&parts.len()) {
+                return None;
+            }
+            let mut secs: u64 = 0;
+            for (i, part) in parts.iter().enumerate() {
+                if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                    return None;
+                }
+                let value = part.parse::<u64>().ok()?;
+                if i > 0 && value > 59 {
+                    return None;
+                }
+                secs = secs.checked_mul(60)?.checked_add(value)?;
+            }
+            return Some(Duration::from_secs(secs));
+        }
+        if cell.is_empty() {
+            return None;
+        }
+        let mut total = Duration::ZERO;
+        let mut rest = cell;
+        while !rest.is_empty() {
+            let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+            if digits == 0 {
+                return None;
+            }
+            let (value, tail) = rest.split_at(digits);
+            let value = value.parse::<u64>().ok()?;
+            let unit_len = tail.find(|c: char| c.is_ascii_digit()).unwrap_or(tail.len());
+            let (unit, tail) = tail.split_at(unit_len);
+            let part = match unit {
+                "d" => Duration::from_secs(value.checked_mul(86400)?),
+                "h" => Duration::from_secs(value.checked_mul(3600)?),
+                "m" => Duration::from_secs(value.checked_mul(60)?),
+                "s" => Duration::from_secs(value),
+                "ms" => Duration::from_millis(value),
+                _ => return None,
+            };
+            total = total.checked_add(part)?;
+            rest = tail;
+        }
+        Some(total)
+    }
+    let Some(cell) = <Option<String> as serde::Deserialize>::deserialize(deserializer)?
+    else {
+        return Ok(None);
+    };
+    parse(&cell)
+        .map(Some)
+        .ok_or_else(|| { serde::de::Error::custom(format!("invalid duration: {cell}")) })
+}
+
+fn serialize_duration<S>(
+    duration: &std::time::Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    fn format(duration: &std::time::Duration) -> String {
+        let mut secs = duration.as_secs();
+        let mut cell = String::new();
+        for (unit, size) in [("d", 86400), ("h", 3600), ("m", 60), ("s", 1)] {
+            if secs >= size {
+                cell.push_str(&format!("{}{unit}", secs / size));
+                secs %= size;
+            }
+        }
+        if duration.subsec_millis() > 0 {
+            cell.push_str(&format!("{}ms", duration.subsec_millis()));
+        }
+        if cell.is_empty() {
+            cell.push_str("0s");
+        }
+        cell
+    }
+    serializer.serialize_str(&format(duration))
+}
+
+fn serialize_map<S>(
+    map: &std::collections::HashMap<String, String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let mut pairs = map.iter().map(|(k, v)| format!("{k}={v}")).collect::<Vec<_>>();
+    pairs.sort();
+    serializer.serialize_str(&pairs.join(";"))
+}
+
+fn serialize_optional_duration<S>(
+    duration: &Option<std::time::Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    fn format(duration: &std::time::Duration) -> String {
+        let mut secs = duration.as_secs();
+        let mut cell = String::new();
+        for (unit, size) in [("d", 86400), ("h", 3600), ("m", 60), ("s", 1)] {
+            if secs >= size {
+                cell.push_str(&format!("{}{unit}", secs / size));
+                secs %= size;
+            }
+        }
+        if duration.subsec_millis() > 0 {
+            cell.push_str(&format!("{}ms", duration.subsec_millis()));
+        }
+        if cell.is_empty() {
+            cell.push_str("0s");
+        }
+        cell
+    }
+    match duration {
+        Some(duration) => serializer.serialize_str(&format(duration)),
+        None => serializer.serialize_none(),
+    }
+}